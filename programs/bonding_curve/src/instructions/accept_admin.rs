@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::emit_event;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    /// Proposed admin, proving it can sign before it takes over
+    pub pending_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        constraint = global_config.pending_admin == Some(pending_admin.key()) @ ErrorCode::NoPendingAdmin,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+pub fn handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config;
+    let previous = global_config.admin;
+
+    global_config.admin = ctx.accounts.pending_admin.key();
+    global_config.pending_admin = None;
+
+    emit_event!(ctx, AuthorityChangedEvent {
+        kind: AuthorityKind::Admin,
+        previous,
+        new: global_config.admin,
+    });
+
+    Ok(())
+}
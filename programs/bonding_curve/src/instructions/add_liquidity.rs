@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, MintTo, TokenInterface, TokenAccount, TransferChecked};
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::emit_event;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AddLiquidity<'info> {
+    #[account(mut)]
+    pub provider: Signer<'info>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"pool",
+            mint.key().as_ref()
+        ],
+        bump = pool.bump,
+        has_one = mint,
+        has_one = lp_mint,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"pool_token_vault",
+            mint.key().as_ref()
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = pool,
+        token::token_program = token_program,
+    )]
+    pub pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == provider.key(),
+        constraint = provider_token_account.mint == mint.key(),
+    )]
+    pub provider_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = provider_lp_account.owner == provider.key(),
+        constraint = provider_lp_account.mint == lp_mint.key(),
+    )]
+    pub provider_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddLiquidity>, lp_amount: u64, max_sol_amount: u64, max_token_amount: u64) -> Result<()> {
+    require!(lp_amount > 0, ErrorCode::InvalidAmount);
+
+    let lp_supply = ctx.accounts.pool.total_lp_supply(ctx.accounts.lp_mint.supply)?;
+    let (sol_amount, token_amount) = ctx.accounts.pool.calculate_deposit(lp_amount, lp_supply)?;
+
+    require!(sol_amount <= max_sol_amount, ErrorCode::SlippageExceeded);
+    require!(token_amount <= max_token_amount, ErrorCode::SlippageExceeded);
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.provider.to_account_info(),
+                to: ctx.accounts.pool.to_account_info(),
+            },
+        ),
+        sol_amount,
+    )?;
+
+    anchor_spl::token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.provider_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.pool_token_vault.to_account_info(),
+                authority: ctx.accounts.provider.to_account_info(),
+            },
+        ),
+        token_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    anchor_spl::token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.provider_lp_account.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            &[&[
+                b"pool",
+                ctx.accounts.mint.key().as_ref(),
+                &[ctx.accounts.pool.bump],
+            ]],
+        ),
+        lp_amount,
+    )?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.sol_reserve = pool.sol_reserve
+        .checked_add(sol_amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    pool.token_reserve = pool.token_reserve
+        .checked_add(token_amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+
+    emit_event!(ctx, LiquidityAddedEvent {
+        mint: ctx.accounts.mint.key(),
+        pool: ctx.accounts.pool.key(),
+        provider: ctx.accounts.provider.key(),
+        lp_amount,
+        sol_amount,
+        token_amount,
+    });
+
+    Ok(())
+}
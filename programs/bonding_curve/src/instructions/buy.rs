@@ -1,42 +1,33 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer, Mint};
-use std::str::FromStr;
+use anchor_spl::token_interface::{TokenInterface, TokenAccount, TransferChecked, Mint};
 use crate::state::*;
 use crate::utils::error::ErrorCode;
-use crate::state::bonding_curve::MIGRATION_THRESHOLD;
-use crate::utils::constants::{TRADE_FEE_BPS, FEE_COLLECTOR, MIGRATION_ADMIN};
+use crate::utils::constants::TRADE_EVENT_VERSION;
+use crate::utils::event_cpi::EventEmitter;
+use crate::utils::trade_fees::{pay_trade_fees, FeePayer};
+use crate::emit_event;
 use anchor_lang::ToAccountInfo;
-use crate::instructions::price;
-use crate::state::events::MigrationEvent;
-use crate::instructions::price::GetPrice;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Buy<'info> {
     #[account(mut)]
     pub buyer: Signer<'info>,
 
-    /// CHECK: Static fee collector address
     #[account(
-        mut,
-        constraint = fee_collector.key() == FEE_COLLECTOR
+        seeds = [b"global_config"],
+        bump = global_config.bump,
     )]
-    pub fee_collector: AccountInfo<'info>,
+    pub global_config: Account<'info, GlobalConfig>,
 
-    /// CHECK: Static migration admin address
+    /// CHECK: Validated against global config
     #[account(
         mut,
-        constraint = migration_admin.key() == MIGRATION_ADMIN
+        constraint = fee_collector.key() == global_config.fee_collector
     )]
-    pub migration_admin: AccountInfo<'info>,
-
-    #[account(
-        mut,
-        constraint = migration_admin_token_account.owner == migration_admin.key(),
-        constraint = migration_admin_token_account.mint == mint.key(),
-    )]
-    pub migration_admin_token_account: Account<'info, TokenAccount>,
+    pub fee_collector: AccountInfo<'info>,
 
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -59,49 +50,114 @@ pub struct Buy<'info> {
         bump,
         token::mint = mint,
         token::authority = curve,
+        token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Buyer's associated token account, whose balance the wallet cap is checked against
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Buyer's subscription, if any. Trade fees are waived while it is active.
+    #[account(
+        seeds = [
+            b"subscription",
+            buyer.key().as_ref()
+        ],
+        bump = subscription.bump,
+    )]
+    pub subscription: Option<Account<'info, Subscription>>,
+
+    /// Receives the creator's share of the trade fee
+    #[account(
+        mut,
+        seeds = [
+            b"creator_fee_vault",
+            curve.key().as_ref()
+        ],
+        bump = creator_fee_vault.bump,
     )]
-    pub token_vault: Account<'info, TokenAccount>,
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
 
+    /// Referrer the trader signed up under, required to pay a referral vault
+    #[account(
+        seeds = [
+            b"trader_referral",
+            buyer.key().as_ref()
+        ],
+        bump = trader_referral.bump,
+    )]
+    pub trader_referral: Option<Account<'info, TraderReferral>>,
+
+    /// Trader's referrer's vault, if any. Receives a share of the trade fee.
     #[account(
         mut,
-        constraint = buyer_token_account.owner == buyer.key(),
-        constraint = buyer_token_account.mint == mint.key(),
+        seeds = [
+            b"referral_vault",
+            referral_vault.referrer.as_ref()
+        ],
+        bump = referral_vault.bump,
+        constraint = trader_referral
+            .as_ref()
+            .is_some_and(|record| record.referrer == referral_vault.referrer)
+            @ ErrorCode::ReferrerMismatch,
     )]
-    pub buyer_token_account: Account<'info, TokenAccount>,
+    pub referral_vault: Option<Account<'info, ReferralVault>>,
 
-    pub token_program: Program<'info, Token>,
+    /// Buyer's allowlist entry, required while the curve's allowlist phase is running
+    #[account(
+        mut,
+        seeds = [
+            b"allowlist_entry",
+            curve.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump = allowlist_entry.bump,
+    )]
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<Buy>, amount: u64, max_sol_cost: u64, is_subscribed: bool) -> Result<()> {
+pub fn handler(ctx: Context<Buy>, amount: u64, max_sol_cost: u64) -> Result<()> {
+    let is_subscribed = is_subscription_active(&ctx.accounts.subscription)?;
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+
     // Check migration status first
     require!(
         ctx.accounts.curve.config.migration_status == MigrationStatus::Active,
         ErrorCode::MigrationComplete
     );
+    require!(ctx.accounts.curve.metadata_initialized, ErrorCode::MetadataNotInitialized);
+
+    check_launch_phase(ctx.accounts, amount)?;
 
-    let curve_lamports = ctx.accounts.curve.to_account_info().lamports();
-    
     // Normal buy transaction first
-    let base_price = ctx.accounts.curve.calculate_buy_price(
-        &ctx.accounts.token_vault,
-        amount,
-        curve_lamports
-    )?;
+    let base_price = ctx.accounts.curve.calculate_buy_price(amount)?;
 
     let (curve_amount, fee_amount) = if !is_subscribed {
-        let fee = (base_price * TRADE_FEE_BPS) / 10000;
+        let fee = ctx.accounts.global_config.calculate_fee(base_price)?;
         (base_price, fee)
     } else {
         (base_price, 0)
     };
+    let (launch_tax_bps, launch_tax) = calculate_launch_tax(ctx.accounts, curve_amount)?;
 
     // Check total cost against max_sol_cost
     let total_cost = curve_amount.checked_add(fee_amount)
+        .and_then(|cost| cost.checked_add(launch_tax))
         .ok_or(error!(ErrorCode::MathOverflow))?;
     require!(total_cost <= max_sol_cost, ErrorCode::PriceExceedsMaxCost);
 
-    // Transfer SOL to curve
+    // Transfer SOL to curve, the launch tax along with it
+    let curve_deposit = curve_amount.checked_add(launch_tax)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
     let transfer_sol_ix = anchor_lang::system_program::Transfer {
         from: ctx.accounts.buyer.to_account_info(),
         to: ctx.accounts.curve.to_account_info(),
@@ -112,31 +168,18 @@ pub fn handler(ctx: Context<Buy>, amount: u64, max_sol_cost: u64, is_subscribed:
             ctx.accounts.system_program.to_account_info(),
             transfer_sol_ix,
         ),
-        curve_amount,
+        curve_deposit,
     )?;
 
-    // Transfer fee if applicable
-    if fee_amount > 0 {
-        let transfer_fee_ix = anchor_lang::system_program::Transfer {
-            from: ctx.accounts.buyer.to_account_info(),
-            to: ctx.accounts.fee_collector.to_account_info(),
-        };
-
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                transfer_fee_ix,
-            ),
-            fee_amount,
-        )?;
-    }
+    let referral_fee = pay_fees(ctx.accounts, fee_amount, &events)?;
 
     // Transfer tokens to buyer
-    anchor_spl::token::transfer(
+    anchor_spl::token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.token_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
                 to: ctx.accounts.buyer_token_account.to_account_info(),
                 authority: ctx.accounts.curve.to_account_info(),
             },
@@ -148,101 +191,65 @@ pub fn handler(ctx: Context<Buy>, amount: u64, max_sol_cost: u64, is_subscribed:
             ]],
         ),
         amount,
+        ctx.accounts.mint.decimals,
     )?;
+    check_buy_limits(ctx.accounts, amount)?;
+
+    ctx.accounts.curve.apply_buy(amount, curve_deposit)?;
 
     // Add after successful token transfer but before migration check
-    emit!(BuyEvent {
+    emit_event!(ctx, BuyEvent {
+        version: TRADE_EVENT_VERSION,
         mint: ctx.accounts.mint.key(),
         amount,
         sol_amount: curve_amount,
         buyer: ctx.accounts.buyer.key(),
-        is_subscribed
+        is_subscribed,
+        referrer: ctx.accounts.referral_vault.as_ref().map(|vault| vault.referrer),
+        referral_fee,
+        is_developer_buy: false,
+        launch_tax_bps,
+        launch_tax,
+        fee: fee_amount,
+        snapshot: ctx.accounts.curve.trade_snapshot()?,
     });
 
-    // NOW check if we need to migrate with the NEW balance
-    let new_curve_lamports = ctx.accounts.curve.to_account_info().lamports();
-    if new_curve_lamports >= MIGRATION_THRESHOLD {
-        // Reload token vault to get current balance after previous transfer
-        ctx.accounts.token_vault.reload()?;
-        let vault_balance = ctx.accounts.token_vault.amount;
-        
-        // Send remaining tokens to admin
-        anchor_spl::token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.token_vault.to_account_info(),
-                    to: ctx.accounts.migration_admin_token_account.to_account_info(),
-                    authority: ctx.accounts.curve.to_account_info(),
-                },
-                &[&[
-                    b"bonding_curve",
-                    ctx.accounts.curve.config.developer.as_ref(),
-                    ctx.accounts.curve.token_seed.as_ref(),
-                    &[ctx.bumps.curve],
-                ]],
-            ),
-            vault_balance,
-        )?;
-
-        // Send all SOL to admin
-        let all_lamports = ctx.accounts.curve.to_account_info().lamports();
-        **ctx.accounts.curve.to_account_info().try_borrow_mut_lamports()? = 0;
-        **ctx.accounts.migration_admin.to_account_info().try_borrow_mut_lamports()? = ctx
-            .accounts
-            .migration_admin
-            .to_account_info()
-            .lamports()
-            .checked_add(all_lamports)
-            .unwrap();
-
-        // Update migration status
-        ctx.accounts.curve.config.migration_status = MigrationStatus::Migrated;
-
-        let effective_price = 1;
-        
-        emit!(MigrationEvent {
-            mint: ctx.accounts.mint.key(),
-            real_sol_amount: new_curve_lamports,
-            virtual_sol_amount: VIRTUAL_SOL_AMOUNT,
-            token_amount: vault_balance,  // Use the saved balance
-            effective_price,
-            developer: ctx.accounts.curve.config.developer,
-            is_subscribed: ctx.accounts.curve.config.is_subscribed
-        });
-    }
+    check_graduation(&mut ctx.accounts.curve, &events)?;
 
     Ok(())
 }
 
-pub fn handler_with_sol(ctx: Context<Buy>, sol_amount: u64, min_token_amount: u64, is_subscribed: bool) -> Result<()> {
+pub fn handler_with_sol(ctx: Context<Buy>, sol_amount: u64, min_token_amount: u64) -> Result<()> {
+    let is_subscribed = is_subscription_active(&ctx.accounts.subscription)?;
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+
     // Check migration status first
     require!(
         ctx.accounts.curve.config.migration_status == MigrationStatus::Active,
         ErrorCode::MigrationComplete
     );
+    require!(ctx.accounts.curve.metadata_initialized, ErrorCode::MetadataNotInitialized);
 
-    let curve_lamports = ctx.accounts.curve.to_account_info().lamports();
-    
     // Calculate token amount from SOL input
-    let token_amount = ctx.accounts.curve.calculate_tokens_for_sol(
-        &ctx.accounts.token_vault,
-        sol_amount,
-        curve_lamports
-    )?;
+    let token_amount = ctx.accounts.curve.calculate_tokens_for_sol(sol_amount)?;
 
     // Verify minimum token amount
     require!(token_amount >= min_token_amount, ErrorCode::SlippageExceeded);
 
+    check_launch_phase(ctx.accounts, token_amount)?;
+
     // Calculate fee using same fee structure
     let (curve_amount, fee_amount) = if !is_subscribed {
-        let fee = (sol_amount * TRADE_FEE_BPS) / 10000;
+        let fee = ctx.accounts.global_config.calculate_fee(sol_amount)?;
         (sol_amount, fee)
     } else {
         (sol_amount, 0)
     };
+    let (launch_tax_bps, launch_tax) = calculate_launch_tax(ctx.accounts, curve_amount)?;
 
-    // Transfer SOL to curve
+    // Transfer SOL to curve, the launch tax along with it
+    let curve_deposit = curve_amount.checked_add(launch_tax)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
     let transfer_sol_ix = anchor_lang::system_program::Transfer {
         from: ctx.accounts.buyer.to_account_info(),
         to: ctx.accounts.curve.to_account_info(),
@@ -253,31 +260,18 @@ pub fn handler_with_sol(ctx: Context<Buy>, sol_amount: u64, min_token_amount: u6
             ctx.accounts.system_program.to_account_info(),
             transfer_sol_ix,
         ),
-        curve_amount,
+        curve_deposit,
     )?;
 
-    // Transfer fee if applicable
-    if fee_amount > 0 {
-        let transfer_fee_ix = anchor_lang::system_program::Transfer {
-            from: ctx.accounts.buyer.to_account_info(),
-            to: ctx.accounts.fee_collector.to_account_info(),
-        };
-
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                transfer_fee_ix,
-            ),
-            fee_amount,
-        )?;
-    }
+    let referral_fee = pay_fees(ctx.accounts, fee_amount, &events)?;
 
     // Transfer tokens to buyer
-    anchor_spl::token::transfer(
+    anchor_spl::token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.token_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
                 to: ctx.accounts.buyer_token_account.to_account_info(),
                 authority: ctx.accounts.curve.to_account_info(),
             },
@@ -289,66 +283,106 @@ pub fn handler_with_sol(ctx: Context<Buy>, sol_amount: u64, min_token_amount: u6
             ]],
         ),
         token_amount,
+        ctx.accounts.mint.decimals,
     )?;
+    check_buy_limits(ctx.accounts, token_amount)?;
+
+    ctx.accounts.curve.apply_buy(token_amount, curve_deposit)?;
 
     // Emit buy event
-    emit!(BuyEvent {
+    emit_event!(ctx, BuyEvent {
+        version: TRADE_EVENT_VERSION,
         mint: ctx.accounts.mint.key(),
         amount: token_amount,
         sol_amount: curve_amount,
         buyer: ctx.accounts.buyer.key(),
-        is_subscribed
+        is_subscribed,
+        referrer: ctx.accounts.referral_vault.as_ref().map(|vault| vault.referrer),
+        referral_fee,
+        is_developer_buy: false,
+        launch_tax_bps,
+        launch_tax,
+        fee: fee_amount,
+        snapshot: ctx.accounts.curve.trade_snapshot()?,
     });
 
-    // Check migration threshold with new balance
-    let new_curve_lamports = ctx.accounts.curve.to_account_info().lamports();
-    if new_curve_lamports >= MIGRATION_THRESHOLD {
-        // Migration logic exactly as in original handler
-        ctx.accounts.token_vault.reload()?;
-        let vault_balance = ctx.accounts.token_vault.amount;
-        
-        // Send remaining tokens to admin
-        anchor_spl::token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.token_vault.to_account_info(),
-                    to: ctx.accounts.migration_admin_token_account.to_account_info(),
-                    authority: ctx.accounts.curve.to_account_info(),
-                },
-                &[&[
-                    b"bonding_curve",
-                    ctx.accounts.curve.config.developer.as_ref(),
-                    ctx.accounts.curve.token_seed.as_ref(),
-                    &[ctx.bumps.curve],
-                ]],
-            ),
-            vault_balance,
-        )?;
-
-        // Send all SOL to admin
-        let all_lamports = ctx.accounts.curve.to_account_info().lamports();
-        **ctx.accounts.curve.to_account_info().try_borrow_mut_lamports()? = 0;
-        **ctx.accounts.migration_admin.to_account_info().try_borrow_mut_lamports()? = ctx
-            .accounts
-            .migration_admin
-            .to_account_info()
-            .lamports()
-            .checked_add(all_lamports)
-            .unwrap();
-
-        ctx.accounts.curve.config.migration_status = MigrationStatus::Migrated;
-
-        emit!(MigrationEvent {
-            mint: ctx.accounts.mint.key(),
-            real_sol_amount: new_curve_lamports,
-            virtual_sol_amount: VIRTUAL_SOL_AMOUNT,
-            token_amount: vault_balance,
-            effective_price: 1,
-            developer: ctx.accounts.curve.config.developer,
-            is_subscribed: ctx.accounts.curve.config.is_subscribed
-        });
+    check_graduation(&mut ctx.accounts.curve, &events)?;
+
+    Ok(())
+}
+
+/// Rejects buys before the curve opens, and during its allowlist phase counts the
+/// purchase against the buyer's cap
+fn check_launch_phase(accounts: &mut Buy, amount: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let config = &accounts.curve.config;
+    require!(now >= config.trading_starts_at, ErrorCode::TradingNotStarted);
+
+    if let Some(allowlist) = config.allowlist {
+        if now < allowlist.ends_at {
+            let entry = accounts.allowlist_entry
+                .as_mut()
+                .ok_or(error!(ErrorCode::NotAllowlisted))?;
+
+            entry.purchased = entry.purchased
+                .checked_add(amount)
+                .ok_or(error!(ErrorCode::MathOverflow))?;
+            require!(entry.purchased <= allowlist.max_tokens_per_wallet, ErrorCode::AllowlistCapExceeded);
+        }
+    }
+
+    Ok(())
+}
+
+/// Launch tax rate and amount owed on top of `curve_amount` at the current slot
+fn calculate_launch_tax(accounts: &Buy, curve_amount: u64) -> Result<(u64, u64)> {
+    let tax_bps = accounts.curve.launch_tax_bps(accounts.global_config.trade_fee_bps, Clock::get()?.slot);
+    let tax = accounts.curve.calculate_launch_tax(curve_amount, tax_bps)?;
+
+    Ok((tax_bps, tax))
+}
+
+/// Checks the curve's holding and per-transaction caps against the buyer's balance
+/// after the tokens have arrived
+fn check_buy_limits(accounts: &mut Buy, amount: u64) -> Result<()> {
+    accounts.buyer_token_account.reload()?;
+    accounts.curve.config.check_buy_limits(amount, accounts.buyer_token_account.amount)
+}
+
+fn pay_fees<'info>(accounts: &mut Buy<'info>, fee_amount: u64, events: &EventEmitter<'info>) -> Result<u64> {
+    pay_trade_fees(
+        &accounts.global_config,
+        accounts.mint.key(),
+        &FeePayer::Wallet {
+            wallet: &accounts.buyer.to_account_info(),
+            system_program: &accounts.system_program.to_account_info(),
+        },
+        &accounts.fee_collector,
+        &mut accounts.creator_fee_vault,
+        accounts.referral_vault.as_mut(),
+        fee_amount,
+        events,
+    )
+}
+
+/// Once the curve holds enough SOL it stops trading and waits for the permissionless
+/// `graduate` instruction to move its liquidity into the program-owned pool
+pub fn check_graduation(curve: &mut Account<BondingCurve>, events: &EventEmitter) -> Result<()> {
+    if curve.is_complete()? {
+        curve.config.migration_status = MigrationStatus::Graduating;
+
+        events.emit(CurveCompletedEvent {
+            mint: curve.mint,
+            curve: curve.key(),
+            real_sol_amount: curve.real_sol_reserves,
+        })?;
     }
 
     Ok(())
 }
+
+/// Subscription status is read from the on-chain `Subscription` account rather than trusted from the caller
+pub fn is_subscription_active(subscription: &Option<Account<Subscription>>) -> Result<bool> {
+    let now = Clock::get()?.unix_timestamp;
+    Ok(subscription.as_ref().is_some_and(|s| s.is_active(now)))
+}
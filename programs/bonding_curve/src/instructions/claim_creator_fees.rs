@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::emit_event;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimCreatorFees<'info> {
+    #[account(mut)]
+    pub developer: Signer<'info>,
+
+    #[account(
+        seeds = [
+            b"bonding_curve",
+            curve.config.developer.as_ref(),
+            curve.token_seed.as_ref()
+        ],
+        bump = curve.bump,
+        constraint = curve.config.developer == developer.key() @ ErrorCode::Unauthorized,
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"creator_fee_vault",
+            curve.key().as_ref()
+        ],
+        bump = creator_fee_vault.bump,
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+}
+
+pub fn handler(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+    let amount = withdraw_fees(
+        &mut ctx.accounts.creator_fee_vault,
+        &ctx.accounts.developer.to_account_info(),
+    )?;
+    let vault = &ctx.accounts.creator_fee_vault;
+
+    emit_event!(ctx, CreatorFeesClaimedEvent {
+        mint: ctx.accounts.curve.mint,
+        curve: ctx.accounts.curve.key(),
+        creator: ctx.accounts.developer.key(),
+        amount,
+        total_claimed: vault.total_claimed,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::emit_event;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"referral_vault",
+            referrer.key().as_ref()
+        ],
+        bump = referral_vault.bump,
+        has_one = referrer @ ErrorCode::Unauthorized,
+    )]
+    pub referral_vault: Account<'info, ReferralVault>,
+}
+
+pub fn handler(ctx: Context<ClaimReferralFees>) -> Result<()> {
+    let amount = withdraw_fees(
+        &mut ctx.accounts.referral_vault,
+        &ctx.accounts.referrer.to_account_info(),
+    )?;
+    let vault = &ctx.accounts.referral_vault;
+
+    emit_event!(ctx, ReferralFeesClaimedEvent {
+        referrer: vault.referrer,
+        referral_vault: vault.key(),
+        amount,
+        total_claimed: vault.total_claimed,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface, TokenAccount, TransferChecked};
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::emit_event;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    pub beneficiary: Signer<'info>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [
+            b"bonding_curve",
+            curve.config.developer.as_ref(),
+            curve.token_seed.as_ref()
+        ],
+        bump = curve.bump,
+        has_one = mint,
+    )]
+    pub curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"creator_lock",
+            curve.key().as_ref()
+        ],
+        bump = creator_lock.bump,
+        has_one = beneficiary @ ErrorCode::Unauthorized,
+    )]
+    pub creator_lock: Box<Account<'info, CreatorLock>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"creator_lock_vault",
+            curve.key().as_ref()
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = curve,
+        token::token_program = token_program,
+    )]
+    pub creator_lock_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = beneficiary_token_account.owner == beneficiary.key(),
+        constraint = beneficiary_token_account.mint == mint.key(),
+    )]
+    pub beneficiary_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Releases everything vested since the last claim
+pub fn handler(ctx: Context<ClaimVested>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let amount = ctx.accounts.creator_lock.claimable_amount(now)?;
+    require!(amount > 0, ErrorCode::NothingToClaim);
+
+    anchor_spl::token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.creator_lock_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                authority: ctx.accounts.curve.to_account_info(),
+            },
+            &[&[
+                b"bonding_curve",
+                ctx.accounts.curve.config.developer.as_ref(),
+                ctx.accounts.curve.token_seed.as_ref(),
+                &[ctx.accounts.curve.bump],
+            ]],
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    let creator_lock = &mut ctx.accounts.creator_lock;
+    creator_lock.claimed_amount = creator_lock.claimed_amount
+        .checked_add(amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+
+    emit_event!(ctx, VestedClaimedEvent {
+        mint: creator_lock.mint,
+        curve: creator_lock.curve,
+        beneficiary: creator_lock.beneficiary,
+        amount,
+        total_claimed: creator_lock.claimed_amount,
+        remaining_locked: creator_lock.locked_amount(now)?,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface, TokenAccount, TransferChecked};
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::emit_event;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CompleteMigration<'info> {
+    #[account(mut)]
+    pub migration_admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        constraint = global_config.migration_admin == migration_admin.key() @ ErrorCode::InvalidMigrationAdmin,
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"migration_escrow",
+            mint.key().as_ref()
+        ],
+        bump = escrow.bump,
+        has_one = mint,
+    )]
+    pub escrow: Box<Account<'info, MigrationEscrow>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"escrow_token_vault",
+            mint.key().as_ref()
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = escrow,
+        token::token_program = token_program,
+    )]
+    pub escrow_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = migration_admin_token_account.owner == migration_admin.key(),
+        constraint = migration_admin_token_account.mint == mint.key(),
+    )]
+    pub migration_admin_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Releases escrowed liquidity to the migration admin, recording the pool it is destined for
+pub fn handler(ctx: Context<CompleteMigration>, destination_pool: Pubkey) -> Result<()> {
+    require!(
+        ctx.accounts.escrow.status == EscrowStatus::Pending,
+        ErrorCode::EscrowNotPending
+    );
+    require!(
+        Clock::get()?.slot <= ctx.accounts.escrow.deadline_slot,
+        ErrorCode::MigrationDeadlinePassed
+    );
+
+    let sol_amount = ctx.accounts.escrow.sol_amount;
+    let token_amount = ctx.accounts.escrow_token_vault.amount;
+
+    anchor_spl::token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow_token_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.migration_admin_token_account.to_account_info(),
+                authority: ctx.accounts.escrow.to_account_info(),
+            },
+            &[&[
+                b"migration_escrow",
+                ctx.accounts.mint.key().as_ref(),
+                &[ctx.accounts.escrow.bump],
+            ]],
+        ),
+        token_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    **ctx.accounts.escrow.to_account_info().try_borrow_mut_lamports()? = ctx
+        .accounts
+        .escrow
+        .to_account_info()
+        .lamports()
+        .checked_sub(sol_amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    **ctx.accounts.migration_admin.to_account_info().try_borrow_mut_lamports()? = ctx
+        .accounts
+        .migration_admin
+        .to_account_info()
+        .lamports()
+        .checked_add(sol_amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.status = EscrowStatus::Completed;
+    escrow.destination_pool = destination_pool;
+
+    emit_event!(ctx, MigrationCompletedEvent {
+        mint: ctx.accounts.mint.key(),
+        escrow: ctx.accounts.escrow.key(),
+        migration_admin: ctx.accounts.migration_admin.key(),
+        destination_pool,
+        sol_amount,
+        token_amount,
+    });
+
+    Ok(())
+}
@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
-use crate::utils::metadata::{create_metadata_ix, validate_metadata_account};
-use crate::state::BondingCurve;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+use crate::utils::metadata::{create_metadata_ix, validate_metadata_account, verify_creator};
+use crate::state::{BondingCurve, MetadataCreatedEvent};
+use crate::utils::error::ErrorCode;
+use crate::instructions::create_token::fix_supply;
+use crate::instructions::initialize_collection::*;
+use crate::emit_event;
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: CreateMetadataParams)]
 pub struct CreateMetadata<'info> {
@@ -10,16 +15,19 @@ pub struct CreateMetadata<'info> {
     pub creator: Signer<'info>,
 
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     /// The curve PDA that controls the token
     #[account(
+        mut,
         seeds = [
             b"bonding_curve",
             creator.key().as_ref(),
             params.token_seed.as_bytes()
         ],
         bump,
+        has_one = mint,
+        constraint = !curve.metadata_initialized @ ErrorCode::MetadataAlreadyInitialized,
     )]
     pub curve: Box<Account<'info, BondingCurve>>,
 
@@ -30,6 +38,9 @@ pub struct CreateMetadata<'info> {
     /// CHECK: Required for metadata creation
     pub metadata_program: AccountInfo<'info>,
 
+    pub collection: LaunchpadCollection<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -58,9 +69,11 @@ pub fn handler(ctx: Context<CreateMetadata>, params: CreateMetadataParams) -> Re
         ctx.accounts.curve.key(),  // Curve as mint authority
         ctx.accounts.creator.key(),
         ctx.accounts.curve.key(),  // Curve as update authority
-        params.name,
-        params.symbol,
-        params.uri,
+        ctx.accounts.creator.key(),
+        ctx.accounts.collection.collection_mint.key(),
+        params.name.clone(),
+        params.symbol.clone(),
+        params.uri.clone(),
     )?;
 
     // Create longer lived values
@@ -91,5 +104,35 @@ pub fn handler(ctx: Context<CreateMetadata>, params: CreateMetadataParams) -> Re
         &[curve_seeds],
     )?;
 
+    verify_creator(
+        &ctx.accounts.metadata,
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.metadata_program,
+    )?;
+    ctx.accounts.collection.verify(
+        &ctx.accounts.metadata,
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.metadata_program,
+        &ctx.bumps.collection,
+    )?;
+
+    ctx.accounts.curve.metadata_initialized = true;
+    emit_event!(ctx, MetadataCreatedEvent {
+        mint: ctx.accounts.mint.key(),
+        curve: ctx.accounts.curve.key(),
+        metadata: ctx.accounts.metadata.key(),
+        name: params.name,
+        symbol: params.symbol,
+        uri: params.uri,
+    });
+
+    // Metaplex needs the mint authority to sign, so it can only be dropped now
+    fix_supply(
+        ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.mint,
+        &mut ctx.accounts.curve,
+        curve_seeds,
+    )?;
+
     Ok(())
 }
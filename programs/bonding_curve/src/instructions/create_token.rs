@@ -1,13 +1,38 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount, MintTo};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenInterface, TokenAccount, MintTo, TransferChecked, SetAuthority};
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
 use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::utils::constants::{TOKEN_DECIMALS, TRADE_EVENT_VERSION};
+use crate::instructions::buy::check_graduation;
+use crate::utils::event_cpi::EventEmitter;
+use crate::utils::trade_fees::{pay_trade_fees, FeePayer};
+use crate::emit_event;
 
+/// Creates the curve, mint and vault, leaving the metadata to a separate `create_metadata`
+/// call. The developer's initial buy, if any, is made here, before the public can trade
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: CreateTokenParams)]
 pub struct CreateToken<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
 
+    /// Provides the defaults for unset curve settings
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    /// CHECK: Validated against global config
+    #[account(
+        mut,
+        constraint = fee_collector.key() == global_config.fee_collector
+    )]
+    pub fee_collector: AccountInfo<'info>,
+
     // Each curve is unique for a token, using creator + token_seed ensures uniqueness
     #[account(
         init,
@@ -32,10 +57,11 @@ pub struct CreateToken<'info> {
             params.token_seed.as_ref()
         ],
         bump,
-        mint::decimals = 6,
+        mint::decimals = TOKEN_DECIMALS,
         mint::authority = curve,
+        mint::token_program = token_program,
     )]
-    pub mint: Box<Account<'info, Mint>>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
 
     // Token vault follows same pattern for consistency
     #[account(
@@ -49,10 +75,62 @@ pub struct CreateToken<'info> {
         bump,
         token::mint = mint,
         token::authority = curve,
+        token::token_program = token_program,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Collects the creator's share of trade fees
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"creator_fee_vault",
+            curve.key().as_ref()
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<CreatorFeeVault>(),
+    )]
+    pub creator_fee_vault: Box<Account<'info, CreatorFeeVault>>,
+
+    /// Receives the developer's initial buy, only needed when making one
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program,
+    )]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Holds the developer's initial buy when they choose to lock it
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"creator_lock",
+            curve.key().as_ref()
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<CreatorLock>(),
+    )]
+    pub creator_lock: Option<Box<Account<'info, CreatorLock>>>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"creator_lock_vault",
+            curve.key().as_ref()
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = curve,
+        token::token_program = token_program,
     )]
-    pub token_vault: Box<Account<'info, TokenAccount>>,
+    pub creator_lock_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -64,21 +142,44 @@ pub struct CreateTokenParams {
     /// Unique identifier for this token, can be name/symbol
     /// Allows creators to make multiple tokens
     pub token_seed: String,
+    /// SOL the developer spends buying in at creation, zero to skip
+    pub initial_buy_sol: u64,
+    /// Locks the initial buy under a vesting schedule instead of sending it to the developer
+    pub developer_lock: Option<LockSchedule>,
 }
 
-pub fn handler(ctx: Context<CreateToken>, params: CreateTokenParams) -> Result<()> {
-    let curve = &mut ctx.accounts.curve;
-    curve.mint = ctx.accounts.mint.key();
-    curve.config = params.curve_config;
-    curve.config.developer = ctx.accounts.creator.key();
-    curve.bump = ctx.bumps.curve;
-    
-    // Clone the token_seed before using it
-    let token_seed = params.token_seed.clone();
-    curve.token_seed = token_seed;
+pub fn handler(ctx: Context<CreateToken>, mut params: CreateTokenParams) -> Result<()> {
+    params.curve_config.prepare(
+        &ctx.accounts.global_config,
+        ctx.accounts.creator.key(),
+        params.total_supply,
+    )?;
+    ctx.accounts.curve.initialize(
+        ctx.accounts.mint.key(),
+        params.curve_config,
+        ctx.bumps.curve,
+        params.token_seed.clone(),
+        params.total_supply,
+        ctx.accounts.global_config.graduation_target,
+    )?;
+
+    let curve_key = ctx.accounts.curve.key();
+    ctx.accounts.creator_fee_vault.initialize(
+        curve_key,
+        ctx.accounts.creator.key(),
+        ctx.bumps.creator_fee_vault,
+    );
+
+    let creator_key = ctx.accounts.creator.key();
+    let signer_seeds: &[&[u8]] = &[
+        b"bonding_curve",
+        creator_key.as_ref(),
+        params.token_seed.as_ref(),
+        &[ctx.bumps.curve],
+    ];
 
     // Mint initial supply to vault
-    anchor_spl::token::mint_to(
+    anchor_spl::token_interface::mint_to(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             MintTo {
@@ -86,15 +187,243 @@ pub fn handler(ctx: Context<CreateToken>, params: CreateTokenParams) -> Result<(
                 to: ctx.accounts.token_vault.to_account_info(),
                 authority: ctx.accounts.curve.to_account_info(),
             },
-            &[&[
-                b"bonding_curve",
-                ctx.accounts.creator.key().as_ref(),
-                params.token_seed.as_ref(),  // Now we can use params.token_seed
-                &[ctx.bumps.curve],
-            ]],
+            &[signer_seeds],
         ),
         params.total_supply,
     )?;
 
+    emit_event!(ctx, TokenCreatedEvent {
+        mint: ctx.accounts.mint.key(),
+        curve: curve_key,
+        developer: ctx.accounts.creator.key(),
+        token_seed: params.token_seed.clone(),
+        total_supply: params.total_supply,
+        curve_config: ctx.accounts.curve.config.clone(),
+        token_program: ctx.accounts.token_program.key(),
+    });
+
+    DeveloperBuy {
+        creator: &ctx.accounts.creator,
+        global_config: &ctx.accounts.global_config,
+        fee_collector: &ctx.accounts.fee_collector,
+        curve: &mut ctx.accounts.curve,
+        mint: &ctx.accounts.mint,
+        token_vault: &ctx.accounts.token_vault,
+        creator_fee_vault: &mut ctx.accounts.creator_fee_vault,
+        creator_token_account: &ctx.accounts.creator_token_account,
+        creator_lock: &mut ctx.accounts.creator_lock,
+        creator_lock_bump: ctx.bumps.creator_lock,
+        creator_lock_vault: &ctx.accounts.creator_lock_vault,
+        token_program: ctx.accounts.token_program.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        events: EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    }.execute(params.initial_buy_sol, params.developer_lock, signer_seeds)?;
+
+    Ok(())
+}
+
+/// Accounts used by a developer's buy while their token is being created. Every launch
+/// path goes through here once the supply is minted
+pub struct DeveloperBuy<'a, 'info> {
+    pub creator: &'a Signer<'info>,
+    pub global_config: &'a GlobalConfig,
+    pub fee_collector: &'a AccountInfo<'info>,
+    pub curve: &'a mut Account<'info, BondingCurve>,
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub creator_fee_vault: &'a mut Account<'info, CreatorFeeVault>,
+    pub creator_token_account: &'a Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub creator_lock: &'a mut Option<Box<Account<'info, CreatorLock>>>,
+    pub creator_lock_bump: Option<u8>,
+    pub creator_lock_vault: &'a Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub events: EventEmitter<'info>,
+}
+
+impl<'a, 'info> DeveloperBuy<'a, 'info> {
+    /// Buys `sol_amount` worth of the new token for the developer, locking it under
+    /// `developer_lock` when one is given. Does nothing when `sol_amount` is zero
+    pub fn execute(
+        mut self,
+        sol_amount: u64,
+        developer_lock: Option<LockSchedule>,
+        signer_seeds: &[&[u8]],
+    ) -> Result<()> {
+        if let Some(schedule) = developer_lock {
+            require!(schedule.validate() && sol_amount > 0, ErrorCode::InvalidLockSchedule);
+        }
+        if sol_amount == 0 {
+            return Ok(());
+        }
+
+        let destination = developer_buy_destination(
+            &developer_lock,
+            self.creator_token_account,
+            self.creator_lock_vault,
+        )?;
+        let token_amount = self.buy(sol_amount, destination, signer_seeds)?;
+
+        if let Some(schedule) = developer_lock {
+            let creator_lock = self.creator_lock
+                .as_mut()
+                .ok_or(error!(ErrorCode::CreatorLockRequired))?;
+            let bump = self.creator_lock_bump.ok_or(error!(ErrorCode::CreatorLockRequired))?;
+
+            lock_developer_buy(self.curve, creator_lock, schedule, token_amount, bump, &self.events)?;
+        }
+
+        Ok(())
+    }
+
+    /// Executes the same curve math, caps and launch tax as `buy_with_sol`, capped to a share
+    /// of the supply, and returns the tokens bought. The developer buys in as the token is
+    /// created, so the delayed trading start and allowlist window, which order the public's
+    /// buys, are bypassed on purpose
+    fn buy(
+        &mut self,
+        sol_amount: u64,
+        destination: &InterfaceAccount<'info, TokenAccount>,
+        signer_seeds: &[&[u8]],
+    ) -> Result<u64> {
+        let token_amount = self.curve.calculate_developer_buy(sol_amount)?;
+        // Whether held or locked, the developer's tokens count against the holding cap
+        self.curve.config.check_buy_limits(token_amount, token_amount)?;
+        let fee_amount = self.global_config.calculate_fee(sol_amount)?;
+        let launch_tax_bps = self.curve.launch_tax_bps(self.global_config.trade_fee_bps, Clock::get()?.slot);
+        let launch_tax = self.curve.calculate_launch_tax(sol_amount, launch_tax_bps)?;
+        let curve_deposit = sol_amount.checked_add(launch_tax)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        // Transfer SOL to curve, the launch tax along with it
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                self.system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: self.creator.to_account_info(),
+                    to: self.curve.to_account_info(),
+                },
+            ),
+            curve_deposit,
+        )?;
+
+        pay_trade_fees(
+            self.global_config,
+            self.mint.key(),
+            &FeePayer::Wallet {
+                wallet: &self.creator.to_account_info(),
+                system_program: &self.system_program,
+            },
+            self.fee_collector,
+            self.creator_fee_vault,
+            None,
+            fee_amount,
+            &self.events,
+        )?;
+
+        // Transfer tokens to the developer
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                TransferChecked {
+                    from: self.token_vault.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: destination.to_account_info(),
+                    authority: self.curve.to_account_info(),
+                },
+                &[signer_seeds],
+            ),
+            token_amount,
+            self.mint.decimals,
+        )?;
+
+        self.curve.apply_buy(token_amount, curve_deposit)?;
+
+        self.events.emit(BuyEvent {
+            version: TRADE_EVENT_VERSION,
+            mint: self.mint.key(),
+            amount: token_amount,
+            sol_amount,
+            buyer: self.creator.key(),
+            is_subscribed: false,
+            referrer: None,
+            referral_fee: 0,
+            is_developer_buy: true,
+            launch_tax_bps,
+            launch_tax,
+            fee: fee_amount,
+            snapshot: self.curve.trade_snapshot()?,
+        })?;
+
+        check_graduation(self.curve, &self.events)?;
+
+        Ok(token_amount)
+    }
+}
+
+/// Locked initial buys go to the creator lock vault, the rest to the developer
+fn developer_buy_destination<'a, 'info>(
+    developer_lock: &Option<LockSchedule>,
+    creator_token_account: &'a Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    creator_lock_vault: &'a Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+) -> Result<&'a InterfaceAccount<'info, TokenAccount>> {
+    let destination = if developer_lock.is_some() {
+        creator_lock_vault.as_deref()
+    } else {
+        creator_token_account.as_deref()
+    };
+
+    destination.ok_or(error!(ErrorCode::DeveloperTokenAccountRequired))
+}
+
+/// Records the developer's initial buy as held in the creator lock
+pub fn lock_developer_buy(
+    curve: &mut Account<BondingCurve>,
+    creator_lock: &mut Account<CreatorLock>,
+    schedule: LockSchedule,
+    amount: u64,
+    bump: u8,
+    events: &EventEmitter,
+) -> Result<()> {
+    creator_lock.initialize(curve.key(), curve.mint, curve.config.developer, amount, schedule, bump)?;
+    curve.has_creator_lock = true;
+
+    events.emit(CreatorLockCreatedEvent {
+        mint: curve.mint,
+        curve: curve.key(),
+        beneficiary: creator_lock.beneficiary,
+        amount,
+        cliff_ts: creator_lock.cliff_ts,
+        end_ts: creator_lock.end_ts,
+    })?;
+
+    Ok(())
+}
+
+/// Drops the curve's mint authority once the supply is minted and the metadata is
+/// written, so the supply can never grow
+pub fn fix_supply<'info>(
+    token_program: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    curve: &mut Account<'info, BondingCurve>,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    require!(mint.freeze_authority.is_none(), ErrorCode::FreezeAuthoritySet);
+
+    anchor_spl::token_interface::set_authority(
+        CpiContext::new_with_signer(
+            token_program,
+            SetAuthority {
+                current_authority: curve.to_account_info(),
+                account_or_mint: mint.to_account_info(),
+            },
+            &[signer_seeds],
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    curve.supply_fixed = true;
+
     Ok(())
 }
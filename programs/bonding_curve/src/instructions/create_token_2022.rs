@@ -0,0 +1,303 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount, MintTo, TokenMetadataInitialize};
+use anchor_spl::token_interface::spl_token_2022::extension::ExtensionType;
+use anchor_spl::token_interface::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_2022_extensions::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::TokenMetadata;
+use crate::state::*;
+use crate::utils::constants::TOKEN_DECIMALS;
+use crate::instructions::create_token::{DeveloperBuy, fix_supply};
+use crate::instructions::initialize_token_group::*;
+use crate::utils::event_cpi::EventEmitter;
+use crate::emit_event;
+
+/// Same accounts as `CreateToken`, but the mint lives under Token-2022 and carries
+/// its own name, symbol and uri, so no Metaplex metadata account is needed. Without
+/// one there is nothing to verify into the launchpad collection, so the mint joins the
+/// launchpad's token group instead
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: CreateToken2022Params)]
+pub struct CreateToken2022<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Provides the defaults for unset curve settings
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    /// CHECK: Validated against global config
+    #[account(
+        mut,
+        constraint = fee_collector.key() == global_config.fee_collector
+    )]
+    pub fee_collector: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"bonding_curve",
+            creator.key().as_ref(),
+            params.token_seed.as_ref()
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<BondingCurve>(),
+    )]
+    pub curve: Box<Account<'info, BondingCurve>>,
+
+    // The metadata pointer points the mint at itself, where the metadata extension lives
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"token_mint",
+            creator.key().as_ref(),
+            params.token_seed.as_ref()
+        ],
+        bump,
+        mint::decimals = TOKEN_DECIMALS,
+        mint::authority = curve,
+        mint::token_program = token_program,
+        extensions::metadata_pointer::authority = curve,
+        extensions::metadata_pointer::metadata_address = mint,
+        extensions::group_member_pointer::authority = curve,
+        extensions::group_member_pointer::member_address = mint,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"token_vault",
+            creator.key().as_ref(),
+            params.token_seed.as_ref()
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = curve,
+        token::token_program = token_program,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Collects the creator's share of trade fees
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"creator_fee_vault",
+            curve.key().as_ref()
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<CreatorFeeVault>(),
+    )]
+    pub creator_fee_vault: Box<Account<'info, CreatorFeeVault>>,
+
+    /// Receives the developer's initial buy, only needed when making one
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program,
+    )]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Holds the developer's initial buy when they choose to lock it
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"creator_lock",
+            curve.key().as_ref()
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<CreatorLock>(),
+    )]
+    pub creator_lock: Option<Box<Account<'info, CreatorLock>>>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"creator_lock_vault",
+            curve.key().as_ref()
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = curve,
+        token::token_program = token_program,
+    )]
+    pub creator_lock_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub group: LaunchpadGroup<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateToken2022Params {
+    pub curve_config: CurveConfig,
+    pub total_supply: u64,
+    /// Unique identifier for this token, can be name/symbol
+    /// Allows creators to make multiple tokens
+    pub token_seed: String,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    /// SOL the developer spends buying in at creation, zero to skip
+    pub initial_buy_sol: u64,
+    /// Locks the initial buy under a vesting schedule instead of sending it to the developer
+    pub developer_lock: Option<LockSchedule>,
+}
+
+pub fn handler(ctx: Context<CreateToken2022>, mut params: CreateToken2022Params) -> Result<()> {
+    params.curve_config.prepare(
+        &ctx.accounts.global_config,
+        ctx.accounts.creator.key(),
+        params.total_supply,
+    )?;
+    ctx.accounts.curve.initialize(
+        ctx.accounts.mint.key(),
+        params.curve_config,
+        ctx.bumps.curve,
+        params.token_seed.clone(),
+        params.total_supply,
+        ctx.accounts.global_config.graduation_target,
+    )?;
+
+    let curve_key = ctx.accounts.curve.key();
+    ctx.accounts.creator_fee_vault.initialize(
+        curve_key,
+        ctx.accounts.creator.key(),
+        ctx.bumps.creator_fee_vault,
+    );
+
+    let signer_seeds: &[&[u8]] = &[
+        b"bonding_curve",
+        ctx.accounts.creator.key.as_ref(),
+        params.token_seed.as_ref(),
+        &[ctx.bumps.curve],
+    ];
+
+    // Group membership is the Token-2022 counterpart of a verified collection, marking
+    // the mint as launched here
+    let mint_info = ctx.accounts.mint.to_account_info();
+    fund_mint_extensions(
+        &ctx.accounts.creator.to_account_info(),
+        &mint_info,
+        &ctx.accounts.system_program.to_account_info(),
+        ExtensionType::try_calculate_account_len::<MintState>(&[
+            ExtensionType::MetadataPointer,
+            ExtensionType::GroupMemberPointer,
+            ExtensionType::TokenGroupMember,
+        ])?,
+    )?;
+    ctx.accounts.group.add_member(
+        &mint_info,
+        &ctx.accounts.curve.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer_seeds,
+        &ctx.bumps.group,
+    )?;
+
+    let metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(curve_key))?,
+        mint: ctx.accounts.mint.key(),
+        name: params.name.clone(),
+        symbol: params.symbol.clone(),
+        uri: params.uri.clone(),
+        additional_metadata: vec![],
+    };
+    fund_mint_extensions(
+        &ctx.accounts.creator.to_account_info(),
+        &mint_info,
+        &ctx.accounts.system_program.to_account_info(),
+        mint_info.data_len() + metadata.tlv_size_of()?,
+    )?;
+
+    anchor_spl::token_interface::token_metadata_initialize(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TokenMetadataInitialize {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                metadata: mint_info.clone(),
+                update_authority: ctx.accounts.curve.to_account_info(),
+                mint_authority: ctx.accounts.curve.to_account_info(),
+                mint: mint_info.clone(),
+            },
+            &[signer_seeds],
+        ),
+        params.name,
+        params.symbol,
+        params.uri,
+    )?;
+    ctx.accounts.curve.metadata_initialized = true;
+    ctx.accounts.curve.native_metadata = true;
+
+    // Mint initial supply to vault
+    anchor_spl::token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: mint_info,
+                to: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.curve.to_account_info(),
+            },
+            &[signer_seeds],
+        ),
+        params.total_supply,
+    )?;
+
+    emit_event!(ctx, TokenCreatedEvent {
+        mint: ctx.accounts.mint.key(),
+        curve: curve_key,
+        developer: ctx.accounts.creator.key(),
+        token_seed: params.token_seed.clone(),
+        total_supply: params.total_supply,
+        curve_config: ctx.accounts.curve.config.clone(),
+        token_program: ctx.accounts.token_program.key(),
+    });
+    emit_event!(ctx, MetadataCreatedEvent {
+        mint: ctx.accounts.mint.key(),
+        curve: curve_key,
+        metadata: ctx.accounts.mint.key(),
+        name: metadata.name,
+        symbol: metadata.symbol,
+        uri: metadata.uri,
+    });
+
+    fix_supply(
+        ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.mint,
+        &mut ctx.accounts.curve,
+        signer_seeds,
+    )?;
+
+    DeveloperBuy {
+        creator: &ctx.accounts.creator,
+        global_config: &ctx.accounts.global_config,
+        fee_collector: &ctx.accounts.fee_collector,
+        curve: &mut ctx.accounts.curve,
+        mint: &ctx.accounts.mint,
+        token_vault: &ctx.accounts.token_vault,
+        creator_fee_vault: &mut ctx.accounts.creator_fee_vault,
+        creator_token_account: &ctx.accounts.creator_token_account,
+        creator_lock: &mut ctx.accounts.creator_lock,
+        creator_lock_bump: ctx.bumps.creator_lock,
+        creator_lock_vault: &ctx.accounts.creator_lock_vault,
+        token_program: ctx.accounts.token_program.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        events: EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    }.execute(params.initial_buy_sol, params.developer_lock, signer_seeds)?;
+
+    Ok(())
+}
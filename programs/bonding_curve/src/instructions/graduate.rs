@@ -0,0 +1,278 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Burn, Mint, TokenInterface, TokenAccount, TransferChecked};
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::utils::metadata::lock_metadata;
+use crate::utils::constants::{POOL_FEE_BPS, LP_DECIMALS, PRICE_SCALE};
+use crate::utils::math::isqrt;
+use crate::emit_event;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Graduate<'info> {
+    /// Anyone can graduate a completed curve, they only pay rent for the pool accounts
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"bonding_curve",
+            curve.config.developer.as_ref(),
+            curve.token_seed.as_ref()
+        ],
+        bump = curve.bump,
+        has_one = mint,
+        constraint = curve.graduation_target == GraduationTarget::Pool @ ErrorCode::InvalidGraduationTarget,
+    )]
+    pub curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"token_vault",
+            curve.config.developer.as_ref(),
+            curve.token_seed.as_ref()
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = curve,
+        token::token_program = token_program,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            b"pool",
+            mint.key().as_ref()
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<Pool>(),
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            b"pool_token_vault",
+            mint.key().as_ref()
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = pool,
+        token::token_program = token_program,
+    )]
+    pub pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            b"lp_mint",
+            mint.key().as_ref()
+        ],
+        bump,
+        mint::decimals = LP_DECIMALS,
+        mint::authority = pool,
+        mint::token_program = token_program,
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Required when the developer locked their initial buy
+    #[account(
+        seeds = [
+            b"creator_lock",
+            curve.key().as_ref()
+        ],
+        bump = creator_lock.bump,
+    )]
+    pub creator_lock: Option<Box<Account<'info, CreatorLock>>>,
+
+    /// CHECK: The developer's subscription PDA, which only exists once they've subscribed.
+    /// Read in instruction, so a caller can't leave it out to hide the subscription
+    #[account(
+        seeds = [
+            b"subscription",
+            curve.config.developer.as_ref()
+        ],
+        bump,
+    )]
+    pub developer_subscription: UncheckedAccount<'info>,
+
+    /// CHECK: The curve's Metaplex metadata, or the mint for Token-2022 metadata. Validated in instruction
+    #[account(mut)]
+    pub metadata: AccountInfo<'info>,
+
+    /// CHECK: Validated in instruction for Metaplex metadata
+    pub metadata_program: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(ctx: Context<Graduate>) -> Result<()> {
+    require!(
+        ctx.accounts.curve.config.migration_status == MigrationStatus::Graduating,
+        ErrorCode::GraduationNotReady
+    );
+
+    let virtual_sol_amount = ctx.accounts.curve.virtual_sol_reserves;
+    let real_token_reserves = ctx.accounts.curve.real_token_reserves;
+
+    // Final spot price of the curve, the pool must open at the same price
+    let spot_price = ctx.accounts.curve.spot_price()?;
+
+    let sol_reserve = ctx.accounts.curve.real_sol_reserves;
+    let token_reserve = ctx.accounts.curve.graduation_pool_tokens()?;
+    let tokens_burned = real_token_reserves
+        .checked_sub(token_reserve)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+
+    require!(sol_reserve > 0 && token_reserve > 0, ErrorCode::InsufficientLiquidity);
+
+    let curve_seeds: &[&[u8]] = &[
+        b"bonding_curve",
+        ctx.accounts.curve.config.developer.as_ref(),
+        ctx.accounts.curve.token_seed.as_ref(),
+        &[ctx.accounts.curve.bump],
+    ];
+
+    // Seed the pool with tokens from the curve vault
+    anchor_spl::token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.token_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.pool_token_vault.to_account_info(),
+                authority: ctx.accounts.curve.to_account_info(),
+            },
+            &[curve_seeds],
+        ),
+        token_reserve,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    // Burn whatever is left so the pool price isn't diluted
+    if tokens_burned > 0 {
+        anchor_spl::token_interface::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.token_vault.to_account_info(),
+                    authority: ctx.accounts.curve.to_account_info(),
+                },
+                &[curve_seeds],
+            ),
+            tokens_burned,
+        )?;
+    }
+
+    retire_curve(
+        &mut ctx.accounts.curve,
+        &ctx.accounts.pool.to_account_info(),
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.metadata,
+        &ctx.accounts.metadata_program,
+        &ctx.accounts.token_program.to_account_info(),
+    )?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.mint = ctx.accounts.mint.key();
+    pool.lp_mint = ctx.accounts.lp_mint.key();
+    pool.sol_reserve = sol_reserve;
+    pool.token_reserve = token_reserve;
+    pool.locked_lp = isqrt(sol_reserve as u128 * token_reserve as u128) as u64;
+    pool.fee_bps = POOL_FEE_BPS;
+    pool.bump = ctx.bumps.pool;
+
+    // Lamports per whole token
+    let effective_price = (spot_price as u128 / PRICE_SCALE) as u64;
+
+    let developer_tokens_locked = developer_tokens_locked(&ctx.accounts.curve, &ctx.accounts.creator_lock)?;
+    let developer_subscribed = developer_subscribed(&ctx.accounts.developer_subscription)?;
+
+    emit_event!(ctx, MigrationEvent {
+        mint: ctx.accounts.mint.key(),
+        pool: ctx.accounts.pool.key(),
+        real_sol_amount: sol_reserve,
+        virtual_sol_amount,
+        token_amount: token_reserve,
+        tokens_burned,
+        effective_price,
+        developer: ctx.accounts.curve.config.developer,
+        developer_subscribed,
+        developer_tokens_locked,
+    });
+
+    Ok(())
+}
+
+/// Developer tokens still vesting in the creator lock when the curve graduates
+pub fn developer_tokens_locked(
+    curve: &BondingCurve,
+    creator_lock: &Option<Box<Account<CreatorLock>>>,
+) -> Result<u64> {
+    if !curve.has_creator_lock {
+        return Ok(0);
+    }
+
+    creator_lock
+        .as_ref()
+        .ok_or(error!(ErrorCode::CreatorLockRequired))?
+        .locked_amount(Clock::get()?.unix_timestamp)
+}
+
+/// Whether the developer's subscription PDA holds an active subscription
+fn developer_subscribed(subscription: &AccountInfo) -> Result<bool> {
+    if subscription.owner != &crate::ID || subscription.data_is_empty() {
+        return Ok(false);
+    }
+
+    let subscription = Subscription::try_deserialize(&mut &subscription.data.borrow()[..])?;
+    Ok(subscription.is_active(Clock::get()?.unix_timestamp))
+}
+
+/// Hands a completed curve's SOL to `destination`, a program-owned account, and closes the
+/// curve for trading. Only the tracked reserves move, donations stay behind for
+/// sweep_excess. Metadata can be edited while trading, but not once the token has graduated
+pub fn retire_curve<'info>(
+    curve: &mut Account<'info, BondingCurve>,
+    destination: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    metadata: &AccountInfo<'info>,
+    metadata_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let sol_amount = curve.real_sol_reserves;
+    let curve_info = curve.to_account_info();
+    let curve_lamports = curve_info.lamports();
+    let destination_lamports = destination.lamports();
+
+    **curve_info.try_borrow_mut_lamports()? = curve_lamports
+        .checked_sub(sol_amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    **destination.try_borrow_mut_lamports()? = destination_lamports
+        .checked_add(sol_amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+
+    curve.real_sol_reserves = 0;
+    curve.real_token_reserves = 0;
+    curve.config.migration_status = MigrationStatus::Migrated;
+
+    lock_metadata(curve, mint, metadata, metadata_program, token_program)
+}
@@ -0,0 +1,173 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface, TokenAccount, TransferChecked};
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::instructions::graduate::{retire_curve, developer_tokens_locked};
+use crate::emit_event;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct GraduateToEscrow<'info> {
+    /// Anyone can graduate a completed curve, they only pay rent for the escrow accounts
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"bonding_curve",
+            curve.config.developer.as_ref(),
+            curve.token_seed.as_ref()
+        ],
+        bump = curve.bump,
+        has_one = mint,
+        constraint = curve.graduation_target == GraduationTarget::Escrow @ ErrorCode::InvalidGraduationTarget,
+    )]
+    pub curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"token_vault",
+            curve.config.developer.as_ref(),
+            curve.token_seed.as_ref()
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = curve,
+        token::token_program = token_program,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            b"migration_escrow",
+            mint.key().as_ref()
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<MigrationEscrow>(),
+    )]
+    pub escrow: Box<Account<'info, MigrationEscrow>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            b"escrow_token_vault",
+            mint.key().as_ref()
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = escrow,
+        token::token_program = token_program,
+    )]
+    pub escrow_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Required when the developer locked their initial buy
+    #[account(
+        seeds = [
+            b"creator_lock",
+            curve.key().as_ref()
+        ],
+        bump = creator_lock.bump,
+    )]
+    pub creator_lock: Option<Box<Account<'info, CreatorLock>>>,
+
+    /// CHECK: The curve's Metaplex metadata, or the mint for Token-2022 metadata. Validated in instruction
+    #[account(mut)]
+    pub metadata: AccountInfo<'info>,
+
+    /// CHECK: Validated in instruction for Metaplex metadata
+    pub metadata_program: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(ctx: Context<GraduateToEscrow>) -> Result<()> {
+    require!(
+        ctx.accounts.curve.config.migration_status == MigrationStatus::Graduating,
+        ErrorCode::GraduationNotReady
+    );
+
+    let vault_balance = ctx.accounts.token_vault.amount;
+    let sol_amount = ctx.accounts.curve.real_sol_reserves;
+    let token_amount = ctx.accounts.curve.real_token_reserves;
+
+    // Tokens in the creator lock count as circulating, so every holder's share of a refund
+    // is fixed now; the developer reclaims theirs with the rest once they have vested
+    let circulating_supply = ctx.accounts.mint.supply
+        .checked_sub(vault_balance)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+
+    // Move the unsold tokens into the escrow
+    anchor_spl::token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.token_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.escrow_token_vault.to_account_info(),
+                authority: ctx.accounts.curve.to_account_info(),
+            },
+            &[&[
+                b"bonding_curve",
+                ctx.accounts.curve.config.developer.as_ref(),
+                ctx.accounts.curve.token_seed.as_ref(),
+                &[ctx.accounts.curve.bump],
+            ]],
+        ),
+        token_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    retire_curve(
+        &mut ctx.accounts.curve,
+        &ctx.accounts.escrow.to_account_info(),
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.metadata,
+        &ctx.accounts.metadata_program,
+        &ctx.accounts.token_program.to_account_info(),
+    )?;
+
+    let developer_tokens_locked = developer_tokens_locked(&ctx.accounts.curve, &ctx.accounts.creator_lock)?;
+
+    let deadline_slot = Clock::get()?.slot
+        .checked_add(ctx.accounts.global_config.migration_timeout_slots)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.mint = ctx.accounts.mint.key();
+    escrow.curve = ctx.accounts.curve.key();
+    escrow.sol_amount = sol_amount;
+    escrow.token_amount = token_amount;
+    escrow.circulating_supply = circulating_supply;
+    escrow.deadline_slot = deadline_slot;
+    escrow.status = EscrowStatus::Pending;
+    escrow.bump = ctx.bumps.escrow;
+
+    emit_event!(ctx, MigrationEscrowedEvent {
+        mint: ctx.accounts.mint.key(),
+        escrow: ctx.accounts.escrow.key(),
+        sol_amount,
+        token_amount,
+        circulating_supply,
+        deadline_slot,
+        developer: ctx.accounts.curve.config.developer,
+        developer_tokens_locked,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,248 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+use mpl_token_metadata::accounts::MasterEdition;
+use mpl_token_metadata::instructions::{
+    CreateMasterEditionV3,
+    CreateMasterEditionV3InstructionArgs,
+    CreateMetadataAccountV3,
+    CreateMetadataAccountV3InstructionArgs,
+    VerifyCollection,
+};
+use mpl_token_metadata::types::DataV2;
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::utils::metadata::find_metadata_account;
+use crate::emit_event;
+
+/// One-time setup of the Onstrument collection NFT every launched token is verified into
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeCollection<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// CHECK: PDA that signs for the collection, holds no data
+    #[account(
+        seeds = [b"collection_authority"],
+        bump,
+    )]
+    pub collection_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"collection_mint"],
+        bump,
+        mint::decimals = 0,
+        mint::authority = collection_authority,
+        mint::freeze_authority = collection_authority,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = collection_mint,
+        associated_token::authority = collection_authority,
+    )]
+    pub collection_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Created by the metadata program
+    #[account(
+        mut,
+        address = find_metadata_account(&collection_mint.key()).0 @ ErrorCode::InvalidMetadataAddress,
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Created by the metadata program
+    #[account(
+        mut,
+        address = MasterEdition::find_pda(&collection_mint.key()).0 @ ErrorCode::InvalidMetadataAddress,
+    )]
+    pub collection_master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the metadata program id
+    #[account(address = mpl_token_metadata::ID @ ErrorCode::InvalidMetadataProgram)]
+    pub metadata_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitializeCollectionParams {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+pub fn handler(ctx: Context<InitializeCollection>, params: InitializeCollectionParams) -> Result<()> {
+    let authority_key = ctx.accounts.collection_authority.key();
+    let authority_seeds: &[&[u8]] = &[
+        b"collection_authority",
+        &[ctx.bumps.collection_authority],
+    ];
+
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.collection_mint.to_account_info(),
+                to: ctx.accounts.collection_token_account.to_account_info(),
+                authority: ctx.accounts.collection_authority.to_account_info(),
+            },
+            &[authority_seeds],
+        ),
+        1,
+    )?;
+
+    let metadata_ix = CreateMetadataAccountV3 {
+        metadata: ctx.accounts.collection_metadata.key(),
+        mint: ctx.accounts.collection_mint.key(),
+        mint_authority: authority_key,
+        payer: ctx.accounts.payer.key(),
+        update_authority: (authority_key, true),
+        system_program: ctx.accounts.system_program.key(),
+        rent: Some(ctx.accounts.rent.key()),
+    }.instruction(CreateMetadataAccountV3InstructionArgs {
+        data: DataV2 {
+            name: params.name,
+            symbol: params.symbol,
+            uri: params.uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        is_mutable: true,
+        collection_details: None,
+    });
+
+    anchor_lang::solana_program::program::invoke_signed(
+        &metadata_ix,
+        &[
+            ctx.accounts.collection_metadata.to_account_info(),
+            ctx.accounts.collection_mint.to_account_info(),
+            ctx.accounts.collection_authority.to_account_info(),
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.metadata_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+        ],
+        &[authority_seeds],
+    )?;
+
+    // The master edition takes over the mint, so the collection can never grow past one NFT
+    let master_edition_ix = CreateMasterEditionV3 {
+        edition: ctx.accounts.collection_master_edition.key(),
+        mint: ctx.accounts.collection_mint.key(),
+        update_authority: authority_key,
+        mint_authority: authority_key,
+        payer: ctx.accounts.payer.key(),
+        metadata: ctx.accounts.collection_metadata.key(),
+        token_program: ctx.accounts.token_program.key(),
+        system_program: ctx.accounts.system_program.key(),
+        rent: Some(ctx.accounts.rent.key()),
+    }.instruction(CreateMasterEditionV3InstructionArgs {
+        max_supply: Some(0),
+    });
+
+    anchor_lang::solana_program::program::invoke_signed(
+        &master_edition_ix,
+        &[
+            ctx.accounts.collection_master_edition.to_account_info(),
+            ctx.accounts.collection_mint.to_account_info(),
+            ctx.accounts.collection_authority.to_account_info(),
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.collection_metadata.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+            ctx.accounts.metadata_program.to_account_info(),
+        ],
+        &[authority_seeds],
+    )?;
+
+    emit_event!(ctx, CollectionInitializedEvent {
+        collection_mint: ctx.accounts.collection_mint.key(),
+        collection_metadata: ctx.accounts.collection_metadata.key(),
+        collection_authority: authority_key,
+    });
+
+    Ok(())
+}
+
+/// The launchpad collection, as needed to verify a new token's metadata into it
+#[derive(Accounts)]
+pub struct LaunchpadCollection<'info> {
+    /// CHECK: PDA that signs for the collection, holds no data
+    #[account(
+        mut,
+        seeds = [b"collection_authority"],
+        bump,
+    )]
+    pub collection_authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"collection_mint"],
+        bump,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Owned by the metadata program
+    #[account(address = find_metadata_account(&collection_mint.key()).0 @ ErrorCode::InvalidMetadataAddress)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Owned by the metadata program
+    #[account(address = MasterEdition::find_pda(&collection_mint.key()).0 @ ErrorCode::InvalidMetadataAddress)]
+    pub collection_master_edition: UncheckedAccount<'info>,
+}
+
+impl<'info> LaunchpadCollection<'info> {
+    /// Marks `metadata` as a verified member of the collection
+    pub fn verify(
+        &self,
+        metadata: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        metadata_program: &AccountInfo<'info>,
+        bumps: &LaunchpadCollectionBumps,
+    ) -> Result<()> {
+        let verify_ix = VerifyCollection {
+            metadata: metadata.key(),
+            collection_authority: self.collection_authority.key(),
+            payer: payer.key(),
+            collection_mint: self.collection_mint.key(),
+            collection: self.collection_metadata.key(),
+            collection_master_edition_account: self.collection_master_edition.key(),
+            collection_authority_record: None,
+        }.instruction();
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &verify_ix,
+            &[
+                metadata.clone(),
+                self.collection_authority.to_account_info(),
+                payer.clone(),
+                self.collection_mint.to_account_info(),
+                self.collection_metadata.to_account_info(),
+                self.collection_master_edition.to_account_info(),
+                metadata_program.clone(),
+            ],
+            &[&[b"collection_authority", &[bumps.collection_authority]]],
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::emit_event;
+use crate::utils::constants::{
+    MAX_TRADE_FEE_BPS,
+    MIN_VIRTUAL_SOL_RESERVES,
+    MAX_VIRTUAL_SOL_RESERVES,
+    MIN_GRADUATION_SOL_TARGET,
+    MAX_GRADUATION_SOL_TARGET,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeGlobal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Only the program's upgrade authority may bootstrap the protocol settings
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"global_config"],
+        bump,
+        space = 8 + std::mem::size_of::<GlobalConfig>(),
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        constraint = bonding_curve_program.programdata_address()? == Some(program_data.key())
+    )]
+    pub bonding_curve_program: Program<'info, crate::program::BondingCurve>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ErrorCode::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitializeGlobalParams {
+    pub admin: Pubkey,
+    pub fee_collector: Pubkey,
+    pub migration_admin: Pubkey,
+    pub trade_fee_bps: u64,
+    pub creator_fee_bps: u64,
+    pub referral_fee_bps: u64,
+    pub migration_threshold: u64,
+    pub virtual_sol_amount: u64,
+    pub graduation_target: GraduationTarget,
+    pub migration_timeout_slots: u64,
+}
+
+pub fn handler(ctx: Context<InitializeGlobal>, params: InitializeGlobalParams) -> Result<()> {
+    require!(params.trade_fee_bps <= MAX_TRADE_FEE_BPS, ErrorCode::InvalidProtocolConfig);
+    require!(
+        params.creator_fee_bps
+            .checked_add(params.referral_fee_bps)
+            .is_some_and(|shares| shares <= params.trade_fee_bps),
+        ErrorCode::InvalidProtocolConfig
+    );
+    require!(
+        (MIN_GRADUATION_SOL_TARGET..=MAX_GRADUATION_SOL_TARGET).contains(&params.migration_threshold),
+        ErrorCode::InvalidProtocolConfig
+    );
+    require!(
+        (MIN_VIRTUAL_SOL_RESERVES..=MAX_VIRTUAL_SOL_RESERVES).contains(&params.virtual_sol_amount),
+        ErrorCode::InvalidProtocolConfig
+    );
+    require!(params.migration_timeout_slots > 0, ErrorCode::InvalidProtocolConfig);
+
+    let global_config = &mut ctx.accounts.global_config;
+    global_config.admin = params.admin;
+    global_config.pending_admin = None;
+    global_config.fee_collector = params.fee_collector;
+    global_config.migration_admin = params.migration_admin;
+    global_config.trade_fee_bps = params.trade_fee_bps;
+    global_config.creator_fee_bps = params.creator_fee_bps;
+    global_config.referral_fee_bps = params.referral_fee_bps;
+    global_config.migration_threshold = params.migration_threshold;
+    global_config.virtual_sol_amount = params.virtual_sol_amount;
+    global_config.graduation_target = params.graduation_target;
+    global_config.migration_timeout_slots = params.migration_timeout_slots;
+    global_config.bump = ctx.bumps.global_config;
+
+    emit_event!(ctx, AuthorityChangedEvent {
+        kind: AuthorityKind::Admin,
+        previous: Pubkey::default(),
+        new: params.admin,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenGroupInitialize, TokenMemberInitialize};
+use anchor_spl::token_interface::spl_token_2022::extension::ExtensionType;
+use anchor_spl::token_interface::spl_token_2022::state::Mint as MintState;
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::emit_event;
+
+/// One-time setup of the Token-2022 group every Token-2022 launch joins, the counterpart
+/// of the collection NFT for mints without Metaplex metadata
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeTokenGroup<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// CHECK: PDA that signs for the group, holds no data
+    #[account(
+        seeds = [b"group_authority"],
+        bump,
+    )]
+    pub group_authority: UncheckedAccount<'info>,
+
+    // The group pointer points the mint at itself, where the group extension lives
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"group_mint"],
+        bump,
+        mint::decimals = 0,
+        mint::authority = group_authority,
+        mint::token_program = token_program,
+        extensions::group_pointer::authority = group_authority,
+        extensions::group_pointer::group_address = group_mint,
+    )]
+    pub group_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeTokenGroup>) -> Result<()> {
+    let authority_key = ctx.accounts.group_authority.key();
+    let group_info = ctx.accounts.group_mint.to_account_info();
+
+    fund_mint_extensions(
+        &ctx.accounts.payer.to_account_info(),
+        &group_info,
+        &ctx.accounts.system_program.to_account_info(),
+        ExtensionType::try_calculate_account_len::<MintState>(&[
+            ExtensionType::GroupPointer,
+            ExtensionType::TokenGroup,
+        ])?,
+    )?;
+
+    anchor_spl::token_interface::token_group_initialize(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TokenGroupInitialize {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                group: group_info.clone(),
+                mint: group_info,
+                mint_authority: ctx.accounts.group_authority.to_account_info(),
+            },
+            &[&[b"group_authority", &[ctx.bumps.group_authority]]],
+        ),
+        Some(authority_key),
+        u32::MAX,
+    )?;
+
+    emit_event!(ctx, TokenGroupInitializedEvent {
+        group_mint: ctx.accounts.group_mint.key(),
+        group_authority: authority_key,
+    });
+
+    Ok(())
+}
+
+/// Tops `account` up to the rent-exempt minimum for `len` bytes. Token-2022 grows a mint
+/// to fit a new extension but leaves the extra rent to the caller
+pub fn fund_mint_extensions<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    len: usize,
+) -> Result<()> {
+    let top_up = Rent::get()?.minimum_balance(len).saturating_sub(account.lamports());
+    if top_up > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// The launchpad's Token-2022 group, as needed to add a new mint to it
+#[derive(Accounts)]
+pub struct LaunchpadGroup<'info> {
+    /// CHECK: PDA that signs for the group, holds no data
+    #[account(
+        seeds = [b"group_authority"],
+        bump,
+    )]
+    pub group_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"group_mint"],
+        bump,
+    )]
+    pub group_mint: Box<InterfaceAccount<'info, Mint>>,
+}
+
+impl<'info> LaunchpadGroup<'info> {
+    /// Writes the group member extension into `mint`, which must carry a member pointer to
+    /// itself. Only the group authority can add members, so membership proves the mint was
+    /// launched here
+    pub fn add_member(
+        &self,
+        mint: &AccountInfo<'info>,
+        mint_authority: &AccountInfo<'info>,
+        token_program: &AccountInfo<'info>,
+        mint_authority_seeds: &[&[u8]],
+        bumps: &LaunchpadGroupBumps,
+    ) -> Result<()> {
+        anchor_spl::token_interface::token_member_initialize(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TokenMemberInitialize {
+                    token_program_id: token_program.clone(),
+                    member: mint.clone(),
+                    member_mint: mint.clone(),
+                    member_mint_authority: mint_authority.clone(),
+                    group: self.group_mint.to_account_info(),
+                    group_update_authority: self.group_authority.to_account_info(),
+                },
+                &[mint_authority_seeds, &[b"group_authority", &[bumps.group_authority]]],
+            ),
+        )
+    }
+}
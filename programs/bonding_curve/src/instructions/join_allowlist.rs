@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::utils::validation::verify_merkle_proof;
+use crate::emit_event;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct JoinAllowlist<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        seeds = [
+            b"bonding_curve",
+            curve.config.developer.as_ref(),
+            curve.token_seed.as_ref()
+        ],
+        bump = curve.bump,
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        init,
+        payer = wallet,
+        seeds = [
+            b"allowlist_entry",
+            curve.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<AllowlistEntry>(),
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Proves the wallet's membership in the curve's allowlist once, so buys during the
+/// allowlist phase only need the resulting entry
+pub fn handler(ctx: Context<JoinAllowlist>, proof: Vec<[u8; 32]>) -> Result<()> {
+    let allowlist = ctx.accounts.curve.config.allowlist
+        .ok_or(error!(ErrorCode::NotAllowlisted))?;
+
+    let leaf = solana_program::keccak::hash(ctx.accounts.wallet.key().as_ref()).0;
+    require!(
+        verify_merkle_proof(&proof, &allowlist.merkle_root, leaf),
+        ErrorCode::InvalidMerkleProof
+    );
+
+    let entry = &mut ctx.accounts.allowlist_entry;
+    entry.curve = ctx.accounts.curve.key();
+    entry.wallet = ctx.accounts.wallet.key();
+    entry.purchased = 0;
+    entry.bump = ctx.bumps.allowlist_entry;
+
+    emit_event!(ctx, AllowlistJoinedEvent {
+        mint: ctx.accounts.curve.mint,
+        curve: entry.curve,
+        wallet: entry.wallet,
+        allowlist_entry: entry.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,294 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, TokenAccount, MintTo};
+use crate::state::*;
+use crate::utils::constants::TOKEN_DECIMALS;
+use crate::utils::metadata::{create_metadata_ix, validate_metadata_account, verify_creator};
+use crate::instructions::initialize_collection::*;
+use crate::instructions::create_token::{DeveloperBuy, fix_supply};
+use crate::utils::event_cpi::EventEmitter;
+use crate::emit_event;
+
+/// Creates the curve, mint, vault and metadata together, so a curve is never
+/// visible without its metadata
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: LaunchParams)]
+pub struct Launch<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    /// CHECK: Validated against global config
+    #[account(
+        mut,
+        constraint = fee_collector.key() == global_config.fee_collector
+    )]
+    pub fee_collector: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"bonding_curve",
+            creator.key().as_ref(),
+            params.token_seed.as_ref()
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<BondingCurve>(),
+    )]
+    pub curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"token_mint",
+            creator.key().as_ref(),
+            params.token_seed.as_ref()
+        ],
+        bump,
+        mint::decimals = TOKEN_DECIMALS,
+        mint::authority = curve,
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"token_vault",
+            creator.key().as_ref(),
+            params.token_seed.as_ref()
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = curve,
+        token::token_program = token_program,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Collects the creator's share of trade fees
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"creator_fee_vault",
+            curve.key().as_ref()
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<CreatorFeeVault>(),
+    )]
+    pub creator_fee_vault: Box<Account<'info, CreatorFeeVault>>,
+
+    /// Receives the developer's initial buy, only needed when making one
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program,
+    )]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Holds the developer's initial buy when they choose to lock it
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"creator_lock",
+            curve.key().as_ref()
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<CreatorLock>(),
+    )]
+    pub creator_lock: Option<Box<Account<'info, CreatorLock>>>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"creator_lock_vault",
+            curve.key().as_ref()
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = curve,
+        token::token_program = token_program,
+    )]
+    pub creator_lock_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: Validated in instruction
+    #[account(mut)]
+    pub metadata: AccountInfo<'info>,
+
+    /// CHECK: Required for metadata creation
+    pub metadata_program: AccountInfo<'info>,
+
+    pub collection: LaunchpadCollection<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LaunchParams {
+    pub curve_config: CurveConfig,
+    pub total_supply: u64,
+    /// Unique identifier for this token, can be name/symbol
+    /// Allows creators to make multiple tokens
+    pub token_seed: String,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    /// SOL the developer spends buying in at launch, zero to skip
+    pub initial_buy_sol: u64,
+    /// Locks the initial buy under a vesting schedule instead of sending it to the developer
+    pub developer_lock: Option<LockSchedule>,
+}
+
+pub fn handler(ctx: Context<Launch>, mut params: LaunchParams) -> Result<()> {
+    params.curve_config.prepare(
+        &ctx.accounts.global_config,
+        ctx.accounts.creator.key(),
+        params.total_supply,
+    )?;
+    ctx.accounts.curve.initialize(
+        ctx.accounts.mint.key(),
+        params.curve_config,
+        ctx.bumps.curve,
+        params.token_seed.clone(),
+        params.total_supply,
+        ctx.accounts.global_config.graduation_target,
+    )?;
+
+    let curve_key = ctx.accounts.curve.key();
+    ctx.accounts.creator_fee_vault.initialize(
+        curve_key,
+        ctx.accounts.creator.key(),
+        ctx.bumps.creator_fee_vault,
+    );
+
+    let creator_key = ctx.accounts.creator.key();
+    let signer_seeds: &[&[u8]] = &[
+        b"bonding_curve",
+        creator_key.as_ref(),
+        params.token_seed.as_ref(),
+        &[ctx.bumps.curve],
+    ];
+
+    // Mint initial supply to vault
+    anchor_spl::token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.curve.to_account_info(),
+            },
+            &[signer_seeds],
+        ),
+        params.total_supply,
+    )?;
+
+    emit_event!(ctx, TokenCreatedEvent {
+        mint: ctx.accounts.mint.key(),
+        curve: curve_key,
+        developer: ctx.accounts.creator.key(),
+        token_seed: params.token_seed.clone(),
+        total_supply: params.total_supply,
+        curve_config: ctx.accounts.curve.config.clone(),
+        token_program: ctx.accounts.token_program.key(),
+    });
+
+    validate_metadata_account(
+        &ctx.accounts.metadata,
+        &ctx.accounts.metadata_program,
+        &ctx.accounts.system_program,
+        &ctx.accounts.mint.key(),
+    )?;
+
+    let metadata_ix = create_metadata_ix(
+        ctx.accounts.metadata.key(),
+        ctx.accounts.mint.key(),
+        curve_key,  // Curve as mint authority
+        creator_key,
+        curve_key,  // Curve as update authority
+        creator_key,
+        ctx.accounts.collection.collection_mint.key(),
+        params.name.clone(),
+        params.symbol.clone(),
+        params.uri.clone(),
+    )?;
+
+    anchor_lang::solana_program::program::invoke_signed(
+        &metadata_ix,
+        &[
+            ctx.accounts.metadata.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.curve.to_account_info(),
+            ctx.accounts.creator.to_account_info(),
+            ctx.accounts.metadata_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+        ],
+        &[signer_seeds],
+    )?;
+
+    verify_creator(
+        &ctx.accounts.metadata,
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.metadata_program,
+    )?;
+    ctx.accounts.collection.verify(
+        &ctx.accounts.metadata,
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.metadata_program,
+        &ctx.bumps.collection,
+    )?;
+
+    ctx.accounts.curve.metadata_initialized = true;
+    emit_event!(ctx, MetadataCreatedEvent {
+        mint: ctx.accounts.mint.key(),
+        curve: ctx.accounts.curve.key(),
+        metadata: ctx.accounts.metadata.key(),
+        name: params.name,
+        symbol: params.symbol,
+        uri: params.uri,
+    });
+
+    fix_supply(
+        ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.mint,
+        &mut ctx.accounts.curve,
+        signer_seeds,
+    )?;
+
+    DeveloperBuy {
+        creator: &ctx.accounts.creator,
+        global_config: &ctx.accounts.global_config,
+        fee_collector: &ctx.accounts.fee_collector,
+        curve: &mut ctx.accounts.curve,
+        mint: &ctx.accounts.mint,
+        token_vault: &ctx.accounts.token_vault,
+        creator_fee_vault: &mut ctx.accounts.creator_fee_vault,
+        creator_token_account: &ctx.accounts.creator_token_account,
+        creator_lock: &mut ctx.accounts.creator_lock,
+        creator_lock_bump: ctx.bumps.creator_lock,
+        creator_lock_vault: &ctx.accounts.creator_lock_vault,
+        token_program: ctx.accounts.token_program.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        events: EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+    }.execute(params.initial_buy_sol, params.developer_lock, signer_seeds)?;
+
+    Ok(())
+}
@@ -1,11 +1,63 @@
+#![allow(ambiguous_glob_reexports)]
+
 pub mod create_token;
+pub mod create_token_2022;
+pub mod launch;
 pub mod create_metadata;
+pub mod update_metadata;
+pub mod initialize_collection;
+pub mod initialize_token_group;
 pub mod buy;
 pub mod sell;
 pub mod price;
+pub mod quote;
+pub mod subscribe;
+pub mod initialize_global;
+pub mod update_global;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod graduate;
+pub mod swap;
+pub mod add_liquidity;
+pub mod remove_liquidity;
+pub mod graduate_to_escrow;
+pub mod complete_migration;
+pub mod reclaim;
+pub mod sweep_excess;
+pub mod claim_creator_fees;
+pub mod register_referrer;
+pub mod set_referrer;
+pub mod claim_referral_fees;
+pub mod claim_vested;
+pub mod join_allowlist;
 
 pub use create_token::*;
+pub use create_token_2022::*;
+pub use launch::*;
 pub use create_metadata::*;
+pub use update_metadata::*;
+pub use initialize_collection::*;
+pub use initialize_token_group::*;
 pub use buy::*;
 pub use sell::*;
-pub use price::*;
\ No newline at end of file
+pub use price::*;
+pub use quote::*;
+pub use subscribe::*;
+pub use initialize_global::*;
+pub use update_global::*;
+pub use propose_admin::*;
+pub use accept_admin::*;
+pub use graduate::*;
+pub use swap::*;
+pub use add_liquidity::*;
+pub use remove_liquidity::*;
+pub use graduate_to_escrow::*;
+pub use complete_migration::*;
+pub use reclaim::*;
+pub use sweep_excess::*;
+pub use claim_creator_fees::*;
+pub use register_referrer::*;
+pub use set_referrer::*;
+pub use claim_referral_fees::*;
+pub use claim_vested::*;
+pub use join_allowlist::*;
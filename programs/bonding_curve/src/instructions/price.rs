@@ -1,11 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount};
 use crate::state::*;
 
 #[derive(Accounts)]
 pub struct GetPrice<'info> {
     /// The mint of the token
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     
     /// The bonding curve account
     #[account(
@@ -30,18 +30,16 @@ pub struct GetPrice<'info> {
         token::mint = mint,
         token::authority = curve,
     )]
-    pub token_vault: Account<'info, TokenAccount>,
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
 }
 
 pub fn calculate_price(ctx: Context<GetPrice>, amount: u64, is_buy: bool) -> Result<u64> {
     let curve = &ctx.accounts.curve;
-    let token_vault = &ctx.accounts.token_vault;
-    let curve_lamports = ctx.accounts.curve.to_account_info().lamports();
 
     if is_buy {
-        curve.calculate_buy_price(token_vault, amount, curve_lamports)
+        curve.calculate_buy_price(amount)
     } else {
-        curve.calculate_sell_price(token_vault, amount, curve_lamports)
+        curve.calculate_sell_price(amount)
     }
 }
 
@@ -50,14 +48,22 @@ pub fn get_migration_status(ctx: Context<GetPrice>) -> Result<MigrationStatus> {
 }
 
 pub fn calculate_tokens_for_sol(ctx: Context<GetPrice>, sol_amount: u64) -> Result<u64> {
-    let curve = &ctx.accounts.curve;
-    let token_vault = &ctx.accounts.token_vault;
-    let curve_lamports = ctx.accounts.curve.to_account_info().lamports();
-
     // Calculate how many tokens the user will receive for their SOL
-    curve.calculate_tokens_for_sol(
-        token_vault,
-        sol_amount,
-        curve_lamports
-    )
+    ctx.accounts.curve.calculate_tokens_for_sol(sol_amount)
+}
+
+pub fn calculate_tokens_for_sol_out(ctx: Context<GetPrice>, sol_amount: u64) -> Result<u64> {
+    // Calculate how many tokens the user must sell to receive their SOL
+    ctx.accounts.curve.calculate_tokens_for_sol_out(sol_amount)
+}
+
+pub fn get_supply_status(ctx: Context<GetPrice>) -> Result<SupplyStatus> {
+    let mint = &ctx.accounts.mint;
+
+    Ok(SupplyStatus {
+        supply_fixed: ctx.accounts.curve.supply_fixed,
+        mint_authority: mint.mint_authority.into(),
+        freeze_authority: mint.freeze_authority.into(),
+        total_supply: mint.supply,
+    })
 }
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::emit_event;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+/// First step of an admin transfer. Passing `None` cancels a pending proposal.
+pub fn handler(ctx: Context<ProposeAdmin>, new_admin: Option<Pubkey>) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config;
+    global_config.pending_admin = new_admin;
+
+    emit_event!(ctx, AdminProposedEvent {
+        admin: global_config.admin,
+        pending_admin: new_admin,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,251 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::instructions::buy::is_subscription_active;
+
+#[derive(Accounts)]
+pub struct GetQuote<'info> {
+    /// The bonding curve account
+    #[account(
+        seeds = [
+            b"bonding_curve",
+            curve.config.developer.as_ref(),
+            curve.token_seed.as_ref()
+        ],
+        bump = curve.bump,
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// CHECK: Only keys the subscription lookup, the quote is for this wallet's trade
+    pub trader: UncheckedAccount<'info>,
+
+    /// Trader's subscription, if any, so the quote reflects waived fees
+    #[account(
+        seeds = [
+            b"subscription",
+            trader.key().as_ref()
+        ],
+        bump = subscription.bump,
+    )]
+    pub subscription: Option<Account<'info, Subscription>>,
+}
+
+pub fn handler(ctx: Context<GetQuote>, direction: TradeDirection, amount: u64) -> Result<Quote> {
+    let is_subscribed = is_subscription_active(&ctx.accounts.subscription)?;
+    let global_config = &ctx.accounts.global_config;
+    let curve = &ctx.accounts.curve;
+
+    require!(
+        curve.config.migration_status == MigrationStatus::Active,
+        ErrorCode::MigrationComplete
+    );
+    require!(curve.metadata_initialized, ErrorCode::MetadataNotInitialized);
+
+    let launch_tax_bps = curve.launch_tax_bps(global_config.trade_fee_bps, Clock::get()?.slot);
+    quote(curve, global_config, is_subscribed, launch_tax_bps, direction, amount)
+}
+
+/// Prices a trade as the trade handlers would settle it, at the given launch tax rate
+fn quote(
+    curve: &BondingCurve,
+    global_config: &GlobalConfig,
+    is_subscribed: bool,
+    launch_tax_bps: u64,
+    direction: TradeDirection,
+    amount: u64,
+) -> Result<Quote> {
+    let fee_on = |amount: u64| -> Result<u64> {
+        if is_subscribed {
+            Ok(0)
+        } else {
+            global_config.calculate_fee(amount)
+        }
+    };
+
+    let mut launch_tax = 0;
+
+    // Mirror the trade handlers on a copy of the curve
+    let mut after = curve.clone();
+    let (input, output, fee) = match direction {
+        TradeDirection::BuyExactOut => {
+            let curve_amount = curve.calculate_buy_price(amount)?;
+            let fee = fee_on(curve_amount)?;
+            launch_tax = curve.calculate_launch_tax(curve_amount, launch_tax_bps)?;
+            let deposit = curve_amount.checked_add(launch_tax).ok_or(error!(ErrorCode::MathOverflow))?;
+            after.apply_buy(amount, deposit)?;
+            let input = deposit.checked_add(fee).ok_or(error!(ErrorCode::MathOverflow))?;
+            (input, amount, fee)
+        }
+        TradeDirection::BuyExactIn => {
+            let token_amount = curve.calculate_tokens_for_sol(amount)?;
+            let fee = fee_on(amount)?;
+            launch_tax = curve.calculate_launch_tax(amount, launch_tax_bps)?;
+            let deposit = amount.checked_add(launch_tax).ok_or(error!(ErrorCode::MathOverflow))?;
+            after.apply_buy(token_amount, deposit)?;
+            let input = deposit.checked_add(fee).ok_or(error!(ErrorCode::MathOverflow))?;
+            (input, token_amount, fee)
+        }
+        TradeDirection::SellExactIn => {
+            let curve_amount = curve.calculate_sell_price(amount)?;
+            let fee = fee_on(curve_amount)?;
+            after.apply_sell(amount, curve_amount)?;
+            let output = curve_amount.checked_sub(fee).ok_or(error!(ErrorCode::MathOverflow))?;
+            (amount, output, fee)
+        }
+        TradeDirection::SellExactOut => {
+            let curve_amount = if is_subscribed {
+                amount
+            } else {
+                global_config.gross_up_for_fee(amount)?
+            };
+            let token_amount = curve.calculate_tokens_for_sol_out(curve_amount)?;
+            after.apply_sell(token_amount, curve_amount)?;
+            let fee = curve_amount.checked_sub(amount).ok_or(error!(ErrorCode::MathOverflow))?;
+            (token_amount, amount, fee)
+        }
+    };
+
+    let spot_price_before = curve.spot_price()?;
+    let spot_price_after = after.spot_price()?;
+    let price_impact_bps = if spot_price_before == 0 {
+        0
+    } else {
+        let impact = (spot_price_after.abs_diff(spot_price_before) as u128) * 10000
+            / spot_price_before as u128;
+        u64::try_from(impact).map_err(|_| error!(ErrorCode::MathOverflow))?
+    };
+
+    Ok(Quote {
+        input,
+        output,
+        fee,
+        launch_tax,
+        price_impact_bps,
+        spot_price_before,
+        spot_price_after,
+        reserves_after: QuoteReserves {
+            real_sol_reserves: after.real_sol_reserves,
+            real_token_reserves: after.real_token_reserves,
+        },
+        would_graduate: after.is_complete()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::test_utils::{curve, global_config, SOL, SUPPLY};
+    use crate::utils::constants::TOKEN_UNIT;
+
+    /// 1% trade fee
+    const TRADE_FEE_BPS: u64 = 100;
+
+    /// Curve with 5 SOL of buys on it, so there is something to sell back
+    fn traded_curve() -> BondingCurve {
+        let mut curve = curve(CurveType::ConstantProduct);
+        let tokens = curve.calculate_tokens_for_sol(5 * SOL).unwrap();
+        curve.apply_buy(tokens, 5 * SOL).unwrap();
+        curve
+    }
+
+    #[test]
+    fn buy_exact_in_matches_the_curve_and_charges_the_fee_on_top() {
+        let curve = curve(CurveType::ConstantProduct);
+        let quote = quote(&curve, &global_config(TRADE_FEE_BPS, 0, 0), false, 0, TradeDirection::BuyExactIn, SOL).unwrap();
+
+        assert_eq!(quote.output, curve.calculate_tokens_for_sol(SOL).unwrap());
+        assert_eq!(quote.fee, SOL / 100);
+        assert_eq!(quote.input, SOL + SOL / 100);
+        assert_eq!(quote.reserves_after.real_sol_reserves, SOL);
+        assert_eq!(quote.reserves_after.real_token_reserves, SUPPLY - quote.output);
+        assert!(quote.spot_price_after > quote.spot_price_before);
+        assert!(quote.price_impact_bps > 0);
+    }
+
+    #[test]
+    fn buy_exact_out_costs_the_curve_price() {
+        let curve = curve(CurveType::ConstantProduct);
+        let tokens = 1_000_000 * TOKEN_UNIT as u64;
+        let quote = quote(&curve, &global_config(TRADE_FEE_BPS, 0, 0), false, 0, TradeDirection::BuyExactOut, tokens).unwrap();
+        let cost = curve.calculate_buy_price(tokens).unwrap();
+
+        assert_eq!(quote.output, tokens);
+        assert_eq!(quote.input, cost + quote.fee);
+        assert_eq!(quote.fee, global_config(TRADE_FEE_BPS, 0, 0).calculate_fee(cost).unwrap());
+    }
+
+    #[test]
+    fn subscribers_are_quoted_without_a_fee() {
+        let curve = traded_curve();
+
+        for direction in [
+            TradeDirection::BuyExactIn,
+            TradeDirection::BuyExactOut,
+            TradeDirection::SellExactIn,
+            TradeDirection::SellExactOut,
+        ] {
+            assert_eq!(quote(&curve, &global_config(TRADE_FEE_BPS, 0, 0), true, 0, direction, SOL).unwrap().fee, 0);
+        }
+    }
+
+    #[test]
+    fn launch_tax_is_paid_into_the_curve() {
+        let curve = curve(CurveType::ConstantProduct);
+        let quote = quote(&curve, &global_config(TRADE_FEE_BPS, 0, 0), false, 1_000, TradeDirection::BuyExactIn, SOL).unwrap();
+
+        assert_eq!(quote.launch_tax, SOL / 10);
+        assert_eq!(quote.input, SOL + SOL / 10 + SOL / 100);
+        assert_eq!(quote.reserves_after.real_sol_reserves, SOL + SOL / 10);
+        // The tax doesn't buy any extra tokens
+        assert_eq!(quote.output, curve.calculate_tokens_for_sol(SOL).unwrap());
+    }
+
+    #[test]
+    fn sell_exact_in_pays_the_curve_price_less_the_fee() {
+        let curve = traded_curve();
+        let tokens = 1_000_000 * TOKEN_UNIT as u64;
+        let quote = quote(&curve, &global_config(TRADE_FEE_BPS, 0, 0), false, 0, TradeDirection::SellExactIn, tokens).unwrap();
+        let proceeds = curve.calculate_sell_price(tokens).unwrap();
+
+        assert_eq!(quote.input, tokens);
+        assert_eq!(quote.output + quote.fee, proceeds);
+        assert_eq!(quote.reserves_after.real_sol_reserves, curve.real_sol_reserves - proceeds);
+        assert!(quote.spot_price_after < quote.spot_price_before);
+    }
+
+    #[test]
+    fn sell_exact_out_pays_exactly_the_amount_asked_for() {
+        let curve = traded_curve();
+        let quote = quote(&curve, &global_config(TRADE_FEE_BPS, 0, 0), false, 0, TradeDirection::SellExactOut, SOL).unwrap();
+
+        assert_eq!(quote.output, SOL);
+        assert_eq!(quote.fee, global_config(TRADE_FEE_BPS, 0, 0).gross_up_for_fee(SOL).unwrap() - SOL);
+        assert_eq!(quote.reserves_after.real_sol_reserves, curve.real_sol_reserves - SOL - quote.fee);
+    }
+
+    #[test]
+    fn buys_leaving_too_few_tokens_for_the_pool_fail() {
+        let curve = curve(CurveType::ConstantProduct);
+        let tokens = SUPPLY - SUPPLY / 1_000;
+
+        let result = quote(&curve, &global_config(TRADE_FEE_BPS, 0, 0), false, 0, TradeDirection::BuyExactOut, tokens);
+        assert_eq!(result.err(), Some(error!(ErrorCode::GraduationLiquidityShortfall)));
+    }
+
+    #[test]
+    fn buys_reaching_the_target_would_graduate() {
+        let curve = curve(CurveType::ConstantProduct);
+
+        let below = quote(&curve, &global_config(TRADE_FEE_BPS, 0, 0), false, 0, TradeDirection::BuyExactIn, 19 * SOL).unwrap();
+        assert!(!below.would_graduate);
+
+        let at = quote(&curve, &global_config(TRADE_FEE_BPS, 0, 0), false, 0, TradeDirection::BuyExactIn, 20 * SOL).unwrap();
+        assert!(at.would_graduate);
+    }
+}
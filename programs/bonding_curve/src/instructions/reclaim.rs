@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Burn, Mint, TokenInterface, TokenAccount};
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::emit_event;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Reclaim<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"migration_escrow",
+            mint.key().as_ref()
+        ],
+        bump = escrow.bump,
+        has_one = mint,
+    )]
+    pub escrow: Box<Account<'info, MigrationEscrow>>,
+
+    #[account(
+        mut,
+        constraint = holder_token_account.owner == holder.key(),
+        constraint = holder_token_account.mint == mint.key(),
+    )]
+    pub holder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Burns tokens for a pro-rata share of the escrowed SOL once the migration admin has missed the deadline
+pub fn handler(ctx: Context<Reclaim>, token_amount: u64) -> Result<()> {
+    require!(token_amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        ctx.accounts.escrow.status == EscrowStatus::Pending,
+        ErrorCode::EscrowNotPending
+    );
+    require!(
+        Clock::get()?.slot > ctx.accounts.escrow.deadline_slot,
+        ErrorCode::MigrationDeadlineNotReached
+    );
+
+    let sol_amount = ctx.accounts.escrow.calculate_refund(token_amount)?;
+
+    anchor_spl::token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.holder_token_account.to_account_info(),
+                authority: ctx.accounts.holder.to_account_info(),
+            },
+        ),
+        token_amount,
+    )?;
+
+    **ctx.accounts.escrow.to_account_info().try_borrow_mut_lamports()? = ctx
+        .accounts
+        .escrow
+        .to_account_info()
+        .lamports()
+        .checked_sub(sol_amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    **ctx.accounts.holder.to_account_info().try_borrow_mut_lamports()? = ctx
+        .accounts
+        .holder
+        .to_account_info()
+        .lamports()
+        .checked_add(sol_amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.tokens_reclaimed = escrow.tokens_reclaimed
+        .checked_add(token_amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    escrow.sol_reclaimed = escrow.sol_reclaimed
+        .checked_add(sol_amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+
+    // Holders can only ever claim the circulating supply they held at graduation
+    require!(
+        escrow.tokens_reclaimed <= escrow.circulating_supply,
+        ErrorCode::InsufficientLiquidity
+    );
+
+    emit_event!(ctx, ReclaimEvent {
+        mint: ctx.accounts.mint.key(),
+        escrow: ctx.accounts.escrow.key(),
+        holder: ctx.accounts.holder.key(),
+        token_amount,
+        sol_amount,
+    });
+
+    Ok(())
+}
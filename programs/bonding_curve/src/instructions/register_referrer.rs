@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::emit_event;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = referrer,
+        seeds = [
+            b"referral_vault",
+            referrer.key().as_ref()
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<ReferralVault>(),
+    )]
+    pub referral_vault: Account<'info, ReferralVault>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let referral_vault = &mut ctx.accounts.referral_vault;
+    referral_vault.referrer = ctx.accounts.referrer.key();
+    referral_vault.total_accrued = 0;
+    referral_vault.total_claimed = 0;
+    referral_vault.bump = ctx.bumps.referral_vault;
+
+    emit_event!(ctx, ReferrerRegisteredEvent {
+        referrer: referral_vault.referrer,
+        referral_vault: referral_vault.key(),
+    });
+
+    Ok(())
+}
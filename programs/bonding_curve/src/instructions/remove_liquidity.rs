@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Burn, Mint, TokenInterface, TokenAccount, TransferChecked};
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::emit_event;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RemoveLiquidity<'info> {
+    #[account(mut)]
+    pub provider: Signer<'info>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"pool",
+            mint.key().as_ref()
+        ],
+        bump = pool.bump,
+        has_one = mint,
+        has_one = lp_mint,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"pool_token_vault",
+            mint.key().as_ref()
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = pool,
+        token::token_program = token_program,
+    )]
+    pub pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == provider.key(),
+        constraint = provider_token_account.mint == mint.key(),
+    )]
+    pub provider_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = provider_lp_account.owner == provider.key(),
+        constraint = provider_lp_account.mint == lp_mint.key(),
+    )]
+    pub provider_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<RemoveLiquidity>, lp_amount: u64, min_sol_amount: u64, min_token_amount: u64) -> Result<()> {
+    require!(lp_amount > 0, ErrorCode::InvalidAmount);
+
+    let lp_supply = ctx.accounts.pool.total_lp_supply(ctx.accounts.lp_mint.supply)?;
+    let (sol_amount, token_amount) = ctx.accounts.pool.calculate_withdrawal(lp_amount, lp_supply)?;
+
+    require!(sol_amount >= min_sol_amount, ErrorCode::SlippageExceeded);
+    require!(token_amount >= min_token_amount, ErrorCode::SlippageExceeded);
+
+    // Burning fails if the provider doesn't hold lp_amount
+    anchor_spl::token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.provider_lp_account.to_account_info(),
+                authority: ctx.accounts.provider.to_account_info(),
+            },
+        ),
+        lp_amount,
+    )?;
+
+    anchor_spl::token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.pool_token_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.provider_token_account.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            &[&[
+                b"pool",
+                ctx.accounts.mint.key().as_ref(),
+                &[ctx.accounts.pool.bump],
+            ]],
+        ),
+        token_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    **ctx.accounts.pool.to_account_info().try_borrow_mut_lamports()? = ctx
+        .accounts
+        .pool
+        .to_account_info()
+        .lamports()
+        .checked_sub(sol_amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    **ctx.accounts.provider.to_account_info().try_borrow_mut_lamports()? = ctx
+        .accounts
+        .provider
+        .to_account_info()
+        .lamports()
+        .checked_add(sol_amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.sol_reserve = pool.sol_reserve
+        .checked_sub(sol_amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    pool.token_reserve = pool.token_reserve
+        .checked_sub(token_amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+
+    emit_event!(ctx, LiquidityRemovedEvent {
+        mint: ctx.accounts.mint.key(),
+        pool: ctx.accounts.pool.key(),
+        provider: ctx.accounts.provider.key(),
+        lp_amount,
+        sol_amount,
+        token_amount,
+    });
+
+    Ok(())
+}
@@ -1,17 +1,20 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer, Mint};
-use solana_program::rent::Rent;
-use std::str::FromStr;
+use anchor_spl::token_interface::{TokenInterface, TokenAccount, TransferChecked, Mint};
 use crate::state::*;
 use crate::utils::error::ErrorCode;
-use crate::utils::constants::{TRADE_FEE_BPS, FEE_COLLECTOR};
+use crate::utils::event_cpi::EventEmitter;
+use crate::utils::constants::TRADE_EVENT_VERSION;
+use crate::utils::trade_fees::{pay_trade_fees, FeePayer};
+use crate::instructions::buy::is_subscription_active;
+use crate::emit_event;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Sell<'info> {
     #[account(mut)]
     pub seller: Signer<'info>,
 
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -29,7 +32,7 @@ pub struct Sell<'info> {
         constraint = seller_token_account.owner == seller.key(),
         constraint = seller_token_account.mint == mint.key(),
     )]
-    pub seller_token_account: Account<'info, TokenAccount>,
+    pub seller_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
@@ -41,64 +44,152 @@ pub struct Sell<'info> {
         bump,
         token::mint = mint,
         token::authority = curve,
+        token::token_program = token_program,
     )]
-    pub token_vault: Account<'info, TokenAccount>,
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
 
-    /// CHECK: Static fee collector address
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// CHECK: Validated against global config
     #[account(
         mut,
-        constraint = fee_collector.key() == FEE_COLLECTOR
+        constraint = fee_collector.key() == global_config.fee_collector
     )]
     pub fee_collector: AccountInfo<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// Seller's subscription, if any. Trade fees are waived while it is active.
+    #[account(
+        seeds = [
+            b"subscription",
+            seller.key().as_ref()
+        ],
+        bump = subscription.bump,
+    )]
+    pub subscription: Option<Account<'info, Subscription>>,
+
+    /// Receives the creator's share of the trade fee
+    #[account(
+        mut,
+        seeds = [
+            b"creator_fee_vault",
+            curve.key().as_ref()
+        ],
+        bump = creator_fee_vault.bump,
+    )]
+    pub creator_fee_vault: Account<'info, CreatorFeeVault>,
+
+    /// Referrer the trader signed up under, required to pay a referral vault
+    #[account(
+        seeds = [
+            b"trader_referral",
+            seller.key().as_ref()
+        ],
+        bump = trader_referral.bump,
+    )]
+    pub trader_referral: Option<Account<'info, TraderReferral>>,
+
+    /// Trader's referrer's vault, if any. Receives a share of the trade fee.
+    #[account(
+        mut,
+        seeds = [
+            b"referral_vault",
+            referral_vault.referrer.as_ref()
+        ],
+        bump = referral_vault.bump,
+        constraint = trader_referral
+            .as_ref()
+            .is_some_and(|record| record.referrer == referral_vault.referrer)
+            @ ErrorCode::ReferrerMismatch,
+    )]
+    pub referral_vault: Option<Account<'info, ReferralVault>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<Sell>, amount: u64, min_sol_return: u64, is_subscribed: bool) -> Result<()> {
+pub fn handler(ctx: Context<Sell>, amount: u64, min_sol_return: u64) -> Result<()> {
+    let is_subscribed = is_subscription_active(&ctx.accounts.subscription)?;
+
     // Check migration status first
     require!(
         ctx.accounts.curve.config.migration_status == MigrationStatus::Active,
         ErrorCode::MigrationComplete
     );
+    require!(ctx.accounts.curve.metadata_initialized, ErrorCode::MetadataNotInitialized);
 
-    // Get the current lamports before mutable borrow
-    let curve_lamports = ctx.accounts.curve.to_account_info().lamports();
-    
-    // Calculate rent-exempt balance for the curve account
-    let rent = Rent::get()?;
-    let rent_exempt_balance = rent.minimum_balance(ctx.accounts.curve.to_account_info().data_len());
-    
-    // Calculate actual available liquidity (excluding rent) - use saturating_sub instead of checked_sub
-    let available_liquidity = curve_lamports.saturating_sub(rent_exempt_balance);
-    
     // Calculate base price and fee
-    let base_price = ctx.accounts.curve.calculate_sell_price(
-        &ctx.accounts.token_vault,
-        amount,
-        curve_lamports
-    )?;
+    let base_price = ctx.accounts.curve.calculate_sell_price(amount)?;
 
     let (curve_amount, fee_amount) = if !is_subscribed {
-        let fee = (base_price * TRADE_FEE_BPS) / 10000;
+        let fee = ctx.accounts.global_config.calculate_fee(base_price)?;
         (base_price, fee)
     } else {
         (base_price, 0)
     };
 
-    // Ensure we don't transfer more than available liquidity
-    let actual_transfer = std::cmp::min(curve_amount, available_liquidity);
-    
     // Check total return against min_sol_return (after fees)
-    let total_return = actual_transfer.saturating_sub(fee_amount);
+    let total_return = curve_amount
+        .checked_sub(fee_amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
     require!(total_return >= min_sol_return, ErrorCode::PriceBelowMinReturn);
 
+    execute_sell(ctx, amount, curve_amount, fee_amount, is_subscribed)
+}
+
+pub fn handler_for_sol(ctx: Context<Sell>, sol_out: u64, max_tokens_in: u64) -> Result<()> {
+    let is_subscribed = is_subscription_active(&ctx.accounts.subscription)?;
+
+    // Check migration status first
+    require!(
+        ctx.accounts.curve.config.migration_status == MigrationStatus::Active,
+        ErrorCode::MigrationComplete
+    );
+    require!(ctx.accounts.curve.metadata_initialized, ErrorCode::MetadataNotInitialized);
+
+    // The curve pays out enough to cover the fee on top of what the seller asked for
+    let curve_amount = if !is_subscribed {
+        ctx.accounts.global_config.gross_up_for_fee(sol_out)?
+    } else {
+        sol_out
+    };
+    let fee_amount = curve_amount
+        .checked_sub(sol_out)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+
+    // Calculate token amount needed for the SOL output
+    let token_amount = ctx.accounts.curve.calculate_tokens_for_sol_out(curve_amount)?;
+
+    // Verify maximum token amount
+    require!(token_amount <= max_tokens_in, ErrorCode::SlippageExceeded);
+
+    execute_sell(ctx, token_amount, curve_amount, fee_amount, is_subscribed)
+}
+
+/// Moves `amount` tokens into the vault and pays `curve_amount` out of the curve,
+/// `fee_amount` of it to the fee collector and the rest to the seller
+fn execute_sell(
+    ctx: Context<Sell>,
+    amount: u64,
+    curve_amount: u64,
+    fee_amount: u64,
+    is_subscribed: bool,
+) -> Result<()> {
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    let total_return = curve_amount
+        .checked_sub(fee_amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+
     // Transfer tokens from seller to vault
-    anchor_spl::token::transfer(
+    anchor_spl::token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.seller_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
                 to: ctx.accounts.token_vault.to_account_info(),
                 authority: ctx.accounts.seller.to_account_info(),
             },
@@ -110,43 +201,40 @@ pub fn handler(ctx: Context<Sell>, amount: u64, min_sol_return: u64, is_subscrib
             ]],
         ),
         amount,
+        ctx.accounts.mint.decimals,
     )?;
 
-    // Transfer SOL from curve to seller and fee collector
-    **ctx.accounts.curve.to_account_info().try_borrow_mut_lamports()? = ctx
-        .accounts
-        .curve
-        .to_account_info()
-        .lamports()
-        .checked_sub(actual_transfer)
-        .ok_or(error!(ErrorCode::MathOverflow))?;
-
-    // Transfer to seller
-    **ctx.accounts.seller.to_account_info().try_borrow_mut_lamports()? = ctx
-        .accounts
-        .seller
-        .to_account_info()
-        .lamports()
-        .checked_add(total_return)
-        .ok_or(error!(ErrorCode::MathOverflow))?;
+    // Pay the seller and the fees out of the curve
+    let curve_info = ctx.accounts.curve.to_account_info();
+    let payer = FeePayer::Curve(&curve_info);
+    payer.pay(&ctx.accounts.seller.to_account_info(), total_return)?;
+
+    let mint = ctx.accounts.mint.key();
+    let referral_fee = pay_trade_fees(
+        &ctx.accounts.global_config,
+        mint,
+        &payer,
+        &ctx.accounts.fee_collector,
+        &mut ctx.accounts.creator_fee_vault,
+        ctx.accounts.referral_vault.as_mut(),
+        fee_amount,
+        &events,
+    )?;
 
-    // Always transfer fee if applicable (even if amount was adjusted)
-    if fee_amount > 0 {
-        **ctx.accounts.fee_collector.try_borrow_mut_lamports()? = ctx
-            .accounts
-            .fee_collector
-            .lamports()
-            .checked_add(actual_transfer.saturating_sub(total_return))
-            .ok_or(error!(ErrorCode::MathOverflow))?;
-    }
+    ctx.accounts.curve.apply_sell(amount, curve_amount)?;
 
     // Add after successful SOL transfer
-    emit!(SellEvent {
+    emit_event!(ctx, SellEvent {
+        version: TRADE_EVENT_VERSION,
         mint: ctx.accounts.mint.key(),
         amount,
         sol_amount: total_return,
         seller: ctx.accounts.seller.key(),
-        is_subscribed
+        is_subscribed,
+        referrer: ctx.accounts.referral_vault.as_ref().map(|vault| vault.referrer),
+        referral_fee,
+        fee: fee_amount,
+        snapshot: ctx.accounts.curve.trade_snapshot()?,
     });
 
     Ok(())
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::emit_event;
+use crate::utils::error::ErrorCode;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetReferrer<'info> {
+    #[account(mut)]
+    pub trader: Signer<'info>,
+
+    #[account(
+        seeds = [
+            b"referral_vault",
+            referral_vault.referrer.as_ref()
+        ],
+        bump = referral_vault.bump,
+        constraint = referral_vault.referrer != trader.key() @ ErrorCode::InvalidReferrer,
+    )]
+    pub referral_vault: Account<'info, ReferralVault>,
+
+    #[account(
+        init,
+        payer = trader,
+        seeds = [
+            b"trader_referral",
+            trader.key().as_ref()
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<TraderReferral>(),
+    )]
+    pub trader_referral: Account<'info, TraderReferral>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Binds the trader to a registered referrer. The record can't be changed afterwards, so
+/// trades can't pick a different referral vault each time.
+pub fn handler(ctx: Context<SetReferrer>) -> Result<()> {
+    let trader_referral = &mut ctx.accounts.trader_referral;
+    trader_referral.trader = ctx.accounts.trader.key();
+    trader_referral.referrer = ctx.accounts.referral_vault.referrer;
+    trader_referral.bump = ctx.bumps.trader_referral;
+
+    emit_event!(ctx, ReferrerSetEvent {
+        trader: trader_referral.trader,
+        referrer: trader_referral.referrer,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::emit_event;
+use crate::utils::constants::{
+    SUBSCRIPTION_PRICE_PER_PERIOD,
+    SUBSCRIPTION_PERIOD_SECONDS,
+    MAX_SUBSCRIPTION_PERIODS,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PurchaseSubscription<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    // One subscription per wallet, renewals extend the same account
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [
+            b"subscription",
+            user.key().as_ref()
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<Subscription>(),
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// CHECK: Validated against global config
+    #[account(
+        mut,
+        constraint = fee_collector.key() == global_config.fee_collector
+    )]
+    pub fee_collector: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<PurchaseSubscription>, periods: u16) -> Result<()> {
+    require!(
+        periods > 0 && periods <= MAX_SUBSCRIPTION_PERIODS,
+        ErrorCode::InvalidSubscriptionPeriods
+    );
+
+    let price = SUBSCRIPTION_PRICE_PER_PERIOD
+        .checked_mul(periods as u64)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+
+    // Pay for the subscription
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.fee_collector.to_account_info(),
+            },
+        ),
+        price,
+    )?;
+
+    // Renewals stack on top of any remaining time, lapsed subscriptions restart from now
+    let now = Clock::get()?.unix_timestamp;
+    let subscription = &mut ctx.accounts.subscription;
+    let starts_at = std::cmp::max(now, subscription.expires_at);
+
+    subscription.user = ctx.accounts.user.key();
+    subscription.bump = ctx.bumps.subscription;
+    subscription.expires_at = starts_at
+        .checked_add(SUBSCRIPTION_PERIOD_SECONDS * periods as i64)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+
+    emit_event!(ctx, SubscriptionPurchasedEvent {
+        user: subscription.user,
+        periods,
+        amount: price,
+        expires_at: subscription.expires_at,
+    });
+
+    Ok(())
+}
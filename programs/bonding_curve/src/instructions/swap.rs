@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface, TokenAccount, TransferChecked};
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::emit_event;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"pool",
+            mint.key().as_ref()
+        ],
+        bump = pool.bump,
+        has_one = mint,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"pool_token_vault",
+            mint.key().as_ref()
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = pool,
+        token::token_program = token_program,
+    )]
+    pub pool_token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key(),
+        constraint = user_token_account.mint == mint.key(),
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<Swap>, amount_in: u64, min_amount_out: u64, is_buy: bool) -> Result<()> {
+    require!(amount_in > 0, ErrorCode::InvalidAmount);
+
+    let amount_out = ctx.accounts.pool.calculate_swap_output(amount_in, is_buy)?;
+    require!(amount_out >= min_amount_out, ErrorCode::SlippageExceeded);
+
+    if is_buy {
+        // SOL in
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.user.to_account_info(),
+                    to: ctx.accounts.pool.to_account_info(),
+                },
+            ),
+            amount_in,
+        )?;
+
+        // Tokens out
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.pool_token_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &[&[
+                    b"pool",
+                    ctx.accounts.mint.key().as_ref(),
+                    &[ctx.accounts.pool.bump],
+                ]],
+            ),
+            amount_out,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        let pool = &mut ctx.accounts.pool;
+        pool.sol_reserve = pool.sol_reserve
+            .checked_add(amount_in)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        pool.token_reserve = pool.token_reserve
+            .checked_sub(amount_out)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+    } else {
+        // Tokens in
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.pool_token_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount_in,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        // SOL out, the pool account is owned by this program
+        **ctx.accounts.pool.to_account_info().try_borrow_mut_lamports()? = ctx
+            .accounts
+            .pool
+            .to_account_info()
+            .lamports()
+            .checked_sub(amount_out)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? = ctx
+            .accounts
+            .user
+            .to_account_info()
+            .lamports()
+            .checked_add(amount_out)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        let pool = &mut ctx.accounts.pool;
+        pool.token_reserve = pool.token_reserve
+            .checked_add(amount_in)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        pool.sol_reserve = pool.sol_reserve
+            .checked_sub(amount_out)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+    }
+
+    emit_event!(ctx, SwapEvent {
+        mint: ctx.accounts.mint.key(),
+        pool: ctx.accounts.pool.key(),
+        user: ctx.accounts.user.key(),
+        is_buy,
+        amount_in,
+        amount_out,
+        sol_reserve: ctx.accounts.pool.sol_reserve,
+        token_reserve: ctx.accounts.pool.token_reserve,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Burn, Mint, TokenInterface, TokenAccount};
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::emit_event;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepExcess<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"bonding_curve",
+            curve.config.developer.as_ref(),
+            curve.token_seed.as_ref()
+        ],
+        bump = curve.bump,
+        has_one = mint,
+    )]
+    pub curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"token_vault",
+            curve.config.developer.as_ref(),
+            curve.token_seed.as_ref()
+        ],
+        bump,
+        token::mint = mint,
+        token::authority = curve,
+        token::token_program = token_program,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Validated against global config
+    #[account(
+        mut,
+        constraint = fee_collector.key() == global_config.fee_collector
+    )]
+    pub fee_collector: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Recovers SOL and burns tokens sent directly to a curve or its vault. Anything above
+/// the tracked reserves (and the curve's rent) was never part of pricing. The tokens are
+/// burned rather than paid out, so they can never be reclaimed from a migration escrow
+/// at the expense of the holders it refunds.
+pub fn handler(ctx: Context<SweepExcess>) -> Result<()> {
+    let curve_info = ctx.accounts.curve.to_account_info();
+    let rent_exempt_balance = Rent::get()?.minimum_balance(curve_info.data_len());
+
+    let excess_sol = curve_info
+        .lamports()
+        .saturating_sub(rent_exempt_balance)
+        .saturating_sub(ctx.accounts.curve.real_sol_reserves);
+    let excess_tokens = ctx.accounts.token_vault.amount
+        .saturating_sub(ctx.accounts.curve.real_token_reserves);
+
+    if excess_tokens > 0 {
+        anchor_spl::token_interface::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.token_vault.to_account_info(),
+                    authority: ctx.accounts.curve.to_account_info(),
+                },
+                &[&[
+                    b"bonding_curve",
+                    ctx.accounts.curve.config.developer.as_ref(),
+                    ctx.accounts.curve.token_seed.as_ref(),
+                    &[ctx.accounts.curve.bump],
+                ]],
+            ),
+            excess_tokens,
+        )?;
+    }
+
+    if excess_sol > 0 {
+        **curve_info.try_borrow_mut_lamports()? = curve_info
+            .lamports()
+            .checked_sub(excess_sol)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        **ctx.accounts.fee_collector.try_borrow_mut_lamports()? = ctx
+            .accounts
+            .fee_collector
+            .lamports()
+            .checked_add(excess_sol)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+    }
+
+    emit_event!(ctx, ExcessSweptEvent {
+        mint: ctx.accounts.mint.key(),
+        curve: ctx.accounts.curve.key(),
+        sol_amount: excess_sol,
+        token_amount: excess_tokens,
+    });
+
+    Ok(())
+}
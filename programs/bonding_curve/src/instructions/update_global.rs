@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::emit_event;
+use crate::utils::constants::{
+    MAX_TRADE_FEE_BPS,
+    MIN_VIRTUAL_SOL_RESERVES,
+    MAX_VIRTUAL_SOL_RESERVES,
+    MIN_GRADUATION_SOL_TARGET,
+    MAX_GRADUATION_SOL_TARGET,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateGlobal<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump = global_config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+/// Only the fields that are set are updated
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateGlobalParams {
+    pub fee_collector: Option<Pubkey>,
+    pub migration_admin: Option<Pubkey>,
+    pub trade_fee_bps: Option<u64>,
+    pub creator_fee_bps: Option<u64>,
+    pub referral_fee_bps: Option<u64>,
+    pub migration_threshold: Option<u64>,
+    pub virtual_sol_amount: Option<u64>,
+    pub graduation_target: Option<GraduationTarget>,
+    pub migration_timeout_slots: Option<u64>,
+}
+
+pub fn handler(ctx: Context<UpdateGlobal>, params: UpdateGlobalParams) -> Result<()> {
+    if let Some(fee_collector) = params.fee_collector {
+        emit_event!(ctx, AuthorityChangedEvent {
+            kind: AuthorityKind::FeeCollector,
+            previous: ctx.accounts.global_config.fee_collector,
+            new: fee_collector,
+        });
+        ctx.accounts.global_config.fee_collector = fee_collector;
+    }
+
+    if let Some(migration_admin) = params.migration_admin {
+        emit_event!(ctx, AuthorityChangedEvent {
+            kind: AuthorityKind::MigrationAdmin,
+            previous: ctx.accounts.global_config.migration_admin,
+            new: migration_admin,
+        });
+        ctx.accounts.global_config.migration_admin = migration_admin;
+    }
+
+    let global_config = &mut ctx.accounts.global_config;
+
+    if let Some(trade_fee_bps) = params.trade_fee_bps {
+        require!(trade_fee_bps <= MAX_TRADE_FEE_BPS, ErrorCode::InvalidProtocolConfig);
+        global_config.trade_fee_bps = trade_fee_bps;
+    }
+
+    if let Some(creator_fee_bps) = params.creator_fee_bps {
+        global_config.creator_fee_bps = creator_fee_bps;
+    }
+
+    if let Some(referral_fee_bps) = params.referral_fee_bps {
+        global_config.referral_fee_bps = referral_fee_bps;
+    }
+
+    // The creator's and referrer's shares are carved out of the trade fee
+    require!(
+        global_config.creator_fee_bps
+            .checked_add(global_config.referral_fee_bps)
+            .is_some_and(|shares| shares <= global_config.trade_fee_bps),
+        ErrorCode::InvalidProtocolConfig
+    );
+
+    if let Some(migration_threshold) = params.migration_threshold {
+        require!(
+            (MIN_GRADUATION_SOL_TARGET..=MAX_GRADUATION_SOL_TARGET).contains(&migration_threshold),
+            ErrorCode::InvalidProtocolConfig
+        );
+        global_config.migration_threshold = migration_threshold;
+    }
+
+    if let Some(virtual_sol_amount) = params.virtual_sol_amount {
+        require!(
+            (MIN_VIRTUAL_SOL_RESERVES..=MAX_VIRTUAL_SOL_RESERVES).contains(&virtual_sol_amount),
+            ErrorCode::InvalidProtocolConfig
+        );
+        global_config.virtual_sol_amount = virtual_sol_amount;
+    }
+
+    if let Some(graduation_target) = params.graduation_target {
+        global_config.graduation_target = graduation_target;
+    }
+
+    if let Some(migration_timeout_slots) = params.migration_timeout_slots {
+        require!(migration_timeout_slots > 0, ErrorCode::InvalidProtocolConfig);
+        global_config.migration_timeout_slots = migration_timeout_slots;
+    }
+
+    Ok(())
+}
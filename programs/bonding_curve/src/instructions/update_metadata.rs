@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as SplMint;
+use anchor_spl::token_interface::{Mint, TokenInterface, TokenMetadataUpdateField};
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::{Field, TokenMetadata};
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::utils::metadata::{find_metadata_account, read_metadata_data, update_metadata_ix};
+use crate::emit_event;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    #[account(mut)]
+    pub developer: Signer<'info>,
+
+    #[account(
+        seeds = [
+            b"bonding_curve",
+            curve.config.developer.as_ref(),
+            curve.token_seed.as_ref()
+        ],
+        bump = curve.bump,
+        has_one = mint,
+        constraint = curve.config.developer == developer.key() @ ErrorCode::Unauthorized,
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: The curve's Metaplex metadata, or the mint for Token-2022 metadata. Validated in instruction
+    #[account(mut)]
+    pub metadata: AccountInfo<'info>,
+
+    /// CHECK: Validated in instruction for Metaplex metadata
+    pub metadata_program: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Fields left as `None` keep their current value
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateMetadataParams {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub uri: Option<String>,
+}
+
+pub fn handler(ctx: Context<UpdateMetadata>, params: UpdateMetadataParams) -> Result<()> {
+    // Metadata is locked for good once the curve graduates
+    require!(
+        ctx.accounts.curve.config.migration_status == MigrationStatus::Active,
+        ErrorCode::MigrationComplete
+    );
+    require!(ctx.accounts.curve.metadata_initialized, ErrorCode::MetadataNotInitialized);
+
+    let curve = &ctx.accounts.curve;
+    let signer_seeds: &[&[u8]] = &[
+        b"bonding_curve",
+        curve.config.developer.as_ref(),
+        curve.token_seed.as_ref(),
+        &[curve.bump],
+    ];
+
+    let (name, symbol, uri) = if curve.native_metadata {
+        update_native_metadata(&ctx, params, signer_seeds)?
+    } else {
+        require!(ctx.accounts.metadata_program.key() == mpl_token_metadata::ID, ErrorCode::InvalidMetadataProgram);
+        require!(
+            ctx.accounts.metadata.key() == find_metadata_account(&ctx.accounts.mint.key()).0,
+            ErrorCode::InvalidMetadataAddress
+        );
+
+        let mut data = read_metadata_data(&ctx.accounts.metadata)?;
+        if let Some(name) = params.name {
+            data.name = name;
+        }
+        if let Some(symbol) = params.symbol {
+            data.symbol = symbol;
+        }
+        if let Some(uri) = params.uri {
+            data.uri = uri;
+        }
+        let updated = (data.name.clone(), data.symbol.clone(), data.uri.clone());
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &update_metadata_ix(ctx.accounts.metadata.key(), curve.key(), Some(data), None),
+            &[
+                ctx.accounts.metadata.clone(),
+                curve.to_account_info(),
+                ctx.accounts.metadata_program.clone(),
+            ],
+            &[signer_seeds],
+        )?;
+
+        updated
+    };
+
+    emit_event!(ctx, MetadataUpdatedEvent {
+        mint: ctx.accounts.mint.key(),
+        curve: ctx.accounts.curve.key(),
+        name,
+        symbol,
+        uri,
+    });
+
+    Ok(())
+}
+
+/// Rewrites the changed fields of the mint's metadata extension, funding any growth in
+/// its size from the developer
+fn update_native_metadata(
+    ctx: &Context<UpdateMetadata>,
+    params: UpdateMetadataParams,
+    signer_seeds: &[&[u8]],
+) -> Result<(String, String, String)> {
+    let mint_info = ctx.accounts.mint.to_account_info();
+    require!(ctx.accounts.metadata.key() == mint_info.key(), ErrorCode::InvalidMetadataAddress);
+
+    let current = {
+        let data = mint_info.try_borrow_data()?;
+        StateWithExtensions::<SplMint>::unpack(&data)?
+            .get_variable_len_extension::<TokenMetadata>()?
+    };
+
+    let mut updated = current.clone();
+    let mut changes = Vec::new();
+    if let Some(name) = params.name {
+        updated.name = name.clone();
+        changes.push((Field::Name, name));
+    }
+    if let Some(symbol) = params.symbol {
+        updated.symbol = symbol.clone();
+        changes.push((Field::Symbol, symbol));
+    }
+    if let Some(uri) = params.uri {
+        updated.uri = uri.clone();
+        changes.push((Field::Uri, uri));
+    }
+
+    // The token program resizes the mint but doesn't fund the extra rent
+    let new_len = (mint_info.data_len() + updated.tlv_size_of()?)
+        .saturating_sub(current.tlv_size_of()?);
+    let top_up = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(mint_info.lamports());
+    if top_up > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.developer.to_account_info(),
+                    to: mint_info.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    for (field, value) in changes {
+        anchor_spl::token_interface::token_metadata_update_field(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TokenMetadataUpdateField {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    metadata: mint_info.clone(),
+                    update_authority: ctx.accounts.curve.to_account_info(),
+                },
+                &[signer_seeds],
+            ),
+            field,
+            value,
+        )?;
+    }
+
+    Ok((updated.name, updated.symbol, updated.uri))
+}
@@ -5,6 +5,7 @@ pub mod state;
 pub mod utils;
 
 use crate::instructions::*;
+use crate::state::{Quote, TradeDirection, SupplyStatus};
 
 declare_id!("6M1WSZeEAGtc8oTkdTNWruMsW58XPByzuf6ayoN16cEq");
 
@@ -16,27 +17,139 @@ pub mod bonding_curve {
         create_token::handler(ctx, params)
     }
 
+    pub fn create_token_2022(ctx: Context<CreateToken2022>, params: CreateToken2022Params) -> Result<()> {
+        create_token_2022::handler(ctx, params)
+    }
+
+    pub fn launch(ctx: Context<Launch>, params: LaunchParams) -> Result<()> {
+        launch::handler(ctx, params)
+    }
+
     pub fn create_metadata(ctx: Context<CreateMetadata>, params: CreateMetadataParams) -> Result<()> {
         create_metadata::handler(ctx, params)
     }
 
-    pub fn buy(ctx: Context<Buy>, amount: u64, max_sol_cost: u64, is_subscribed: bool) -> Result<()> {
-        buy::handler(ctx, amount, max_sol_cost, is_subscribed)
+    pub fn update_metadata(ctx: Context<UpdateMetadata>, params: UpdateMetadataParams) -> Result<()> {
+        update_metadata::handler(ctx, params)
+    }
+
+    pub fn buy(ctx: Context<Buy>, amount: u64, max_sol_cost: u64) -> Result<()> {
+        buy::handler(ctx, amount, max_sol_cost)
+    }
+
+    pub fn sell(ctx: Context<Sell>, amount: u64, min_sol_return: u64) -> Result<()> {
+        sell::handler(ctx, amount, min_sol_return)
     }
 
-    pub fn sell(ctx: Context<Sell>, amount: u64, min_sol_return: u64, is_subscribed: bool) -> Result<()> {
-        sell::handler(ctx, amount, min_sol_return, is_subscribed)
+    pub fn sell_for_sol(ctx: Context<Sell>, sol_out: u64, max_tokens_in: u64) -> Result<()> {
+        sell::handler_for_sol(ctx, sol_out, max_tokens_in)
     }
 
     pub fn calculate_price(ctx: Context<GetPrice>, amount: u64, is_buy: bool) -> Result<u64> {
         price::calculate_price(ctx, amount, is_buy)
     }
 
-    pub fn buy_with_sol(ctx: Context<Buy>, sol_amount: u64, min_token_amount: u64, is_subscribed: bool) -> Result<()> {
-        buy::handler_with_sol(ctx, sol_amount, min_token_amount, is_subscribed)
+    pub fn buy_with_sol(ctx: Context<Buy>, sol_amount: u64, min_token_amount: u64) -> Result<()> {
+        buy::handler_with_sol(ctx, sol_amount, min_token_amount)
     }
 
     pub fn calculate_tokens_for_sol(ctx: Context<GetPrice>, sol_amount: u64) -> Result<u64> {
         price::calculate_tokens_for_sol(ctx, sol_amount)
     }
+
+    pub fn calculate_tokens_for_sol_out(ctx: Context<GetPrice>, sol_amount: u64) -> Result<u64> {
+        price::calculate_tokens_for_sol_out(ctx, sol_amount)
+    }
+
+    pub fn get_supply_status(ctx: Context<GetPrice>) -> Result<SupplyStatus> {
+        price::get_supply_status(ctx)
+    }
+
+    pub fn quote(ctx: Context<GetQuote>, direction: TradeDirection, amount: u64) -> Result<Quote> {
+        quote::handler(ctx, direction, amount)
+    }
+
+    pub fn purchase_subscription(ctx: Context<PurchaseSubscription>, periods: u16) -> Result<()> {
+        subscribe::handler(ctx, periods)
+    }
+
+    pub fn initialize_global(ctx: Context<InitializeGlobal>, params: InitializeGlobalParams) -> Result<()> {
+        initialize_global::handler(ctx, params)
+    }
+
+    pub fn update_global(ctx: Context<UpdateGlobal>, params: UpdateGlobalParams) -> Result<()> {
+        update_global::handler(ctx, params)
+    }
+
+    pub fn initialize_collection(ctx: Context<InitializeCollection>, params: InitializeCollectionParams) -> Result<()> {
+        initialize_collection::handler(ctx, params)
+    }
+
+    pub fn initialize_token_group(ctx: Context<InitializeTokenGroup>) -> Result<()> {
+        initialize_token_group::handler(ctx)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Option<Pubkey>) -> Result<()> {
+        propose_admin::handler(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        accept_admin::handler(ctx)
+    }
+
+    pub fn graduate(ctx: Context<Graduate>) -> Result<()> {
+        graduate::handler(ctx)
+    }
+
+    pub fn swap(ctx: Context<Swap>, amount_in: u64, min_amount_out: u64, is_buy: bool) -> Result<()> {
+        swap::handler(ctx, amount_in, min_amount_out, is_buy)
+    }
+
+    pub fn add_liquidity(ctx: Context<AddLiquidity>, lp_amount: u64, max_sol_amount: u64, max_token_amount: u64) -> Result<()> {
+        add_liquidity::handler(ctx, lp_amount, max_sol_amount, max_token_amount)
+    }
+
+    pub fn remove_liquidity(ctx: Context<RemoveLiquidity>, lp_amount: u64, min_sol_amount: u64, min_token_amount: u64) -> Result<()> {
+        remove_liquidity::handler(ctx, lp_amount, min_sol_amount, min_token_amount)
+    }
+
+    pub fn graduate_to_escrow(ctx: Context<GraduateToEscrow>) -> Result<()> {
+        graduate_to_escrow::handler(ctx)
+    }
+
+    pub fn complete_migration(ctx: Context<CompleteMigration>, destination_pool: Pubkey) -> Result<()> {
+        complete_migration::handler(ctx, destination_pool)
+    }
+
+    pub fn reclaim(ctx: Context<Reclaim>, token_amount: u64) -> Result<()> {
+        reclaim::handler(ctx, token_amount)
+    }
+
+    pub fn sweep_excess(ctx: Context<SweepExcess>) -> Result<()> {
+        sweep_excess::handler(ctx)
+    }
+
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+        claim_creator_fees::handler(ctx)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        register_referrer::handler(ctx)
+    }
+
+    pub fn set_referrer(ctx: Context<SetReferrer>) -> Result<()> {
+        set_referrer::handler(ctx)
+    }
+
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+        claim_referral_fees::handler(ctx)
+    }
+
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        claim_vested::handler(ctx)
+    }
+
+    pub fn join_allowlist(ctx: Context<JoinAllowlist>, proof: Vec<[u8; 32]>) -> Result<()> {
+        join_allowlist::handler(ctx, proof)
+    }
 }
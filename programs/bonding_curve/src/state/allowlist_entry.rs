@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// Proof that a wallet is on a curve's allowlist, and how much it has bought during the
+/// allowlist phase
+#[account]
+pub struct AllowlistEntry {
+    pub curve: Pubkey,
+    pub wallet: Pubkey,
+    /// Tokens bought while the allowlist phase was running
+    pub purchased: u64,
+    pub bump: u8,
+}
@@ -1,129 +1,796 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
 use crate::utils::error::ErrorCode;
-use crate::state::curve_config::CurveConfig;
-
-pub const MIGRATION_THRESHOLD: u64 = 80_000_000_000; // 80 SOL in lamports
-pub const VIRTUAL_SOL_AMOUNT: u64 = 30_000_000_000; // 30 SOL in lamports
+use crate::utils::constants::{TOKEN_UNIT, SLOPE_SCALE, PRICE_SCALE, MAX_DEVELOPER_BUY_BPS};
+use crate::utils::math::{ceil_div, mul_div, exp2_wad, log2_wad, WAD, LN2_WAD};
+use crate::state::curve_config::{CurveConfig, CurveType};
+use crate::state::global_config::GraduationTarget;
+use crate::state::events::TradeSnapshot;
 
 #[account]
+#[derive(Default)]
 pub struct BondingCurve {
     pub mint: Pubkey,
     pub config: CurveConfig,
     pub bump: u8,
     pub token_seed: String,
+    /// SOL paid in by buyers and not yet paid out to sellers
+    pub real_sol_reserves: u64,
+    /// Tokens left in the vault for sale
+    pub real_token_reserves: u64,
+    /// SOL added on top of the real reserves when pricing
+    pub virtual_sol_reserves: u64,
+    /// Tokens added on top of the real reserves when pricing
+    pub virtual_token_reserves: u64,
+    /// Tokens minted into the vault at creation
+    pub token_total_supply: u64,
+    /// Trading stays closed until the token's metadata exists
+    pub metadata_initialized: bool,
+    /// Developer's initial buy is held in a `CreatorLock`
+    pub has_creator_lock: bool,
+    /// Mint authority has been revoked, so no more tokens can ever be minted
+    pub supply_fixed: bool,
+    /// Metadata lives in the Token-2022 mint rather than a Metaplex account
+    pub native_metadata: bool,
+    /// Slot the curve was created in, which the launch tax decays from
+    pub created_slot: u64,
+    /// Trades settled against the curve so far, numbering its trade events
+    pub trade_sequence: u64,
+    /// Protocol graduation target when the curve was created, so later changes don't
+    /// move live curves
+    pub graduation_target: GraduationTarget,
 }
 
 impl BondingCurve {
-    pub fn get_effective_amounts(&self, token_vault: &Account<TokenAccount>, curve_lamports: u64) -> Result<(u64, u64)> {
-        // Now using constant VIRTUAL_SOL_AMOUNT instead of config value
-        let effective_sol = curve_lamports
-            .checked_add(VIRTUAL_SOL_AMOUNT)
+    /// The whole supply starts in the vault, priced against the creator's virtual reserves
+    pub fn initialize(
+        &mut self,
+        mint: Pubkey,
+        config: CurveConfig,
+        bump: u8,
+        token_seed: String,
+        total_supply: u64,
+        graduation_target: GraduationTarget,
+    ) -> Result<()> {
+        self.mint = mint;
+        self.bump = bump;
+        self.token_seed = token_seed;
+        self.real_sol_reserves = 0;
+        self.real_token_reserves = total_supply;
+        self.virtual_sol_reserves = config.virtual_sol_reserves;
+        self.virtual_token_reserves = config.virtual_token_reserves;
+        self.token_total_supply = total_supply;
+        self.metadata_initialized = false;
+        self.has_creator_lock = false;
+        self.supply_fixed = false;
+        self.native_metadata = false;
+        self.created_slot = Clock::get()?.slot;
+        self.trade_sequence = 0;
+        self.graduation_target = graduation_target;
+        self.config = config;
+
+        Ok(())
+    }
+
+    /// Launch tax charged on top of the trade fee at `slot`, in bps. It starts at the
+    /// configured rate less the trade fee and falls linearly to zero over the decay period
+    pub fn launch_tax_bps(&self, trade_fee_bps: u64, slot: u64) -> u64 {
+        let Some(tax) = self.config.launch_tax else {
+            return 0;
+        };
+
+        let elapsed = slot.saturating_sub(self.created_slot);
+        if elapsed >= tax.decay_slots {
+            return 0;
+        }
+
+        let initial = tax.start_bps.saturating_sub(trade_fee_bps);
+        (initial as u128 * (tax.decay_slots - elapsed) as u128 / tax.decay_slots as u128) as u64
+    }
+
+    pub fn calculate_launch_tax(&self, amount: u64, tax_bps: u64) -> Result<u64> {
+        let tax = (amount as u128)
+            .checked_mul(tax_bps as u128)
+            .ok_or(error!(ErrorCode::MathOverflow))?
+            / 10000;
+
+        Ok(tax as u64)
+    }
+
+    /// Curve with `config` as it is at creation, with all of `total_supply` unsold
+    fn fresh(config: CurveConfig, total_supply: u64) -> Self {
+        BondingCurve {
+            real_token_reserves: total_supply,
+            virtual_sol_reserves: config.virtual_sol_reserves,
+            virtual_token_reserves: config.virtual_token_reserves,
+            token_total_supply: total_supply,
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// SOL a fresh curve with `config` raises by selling all of `total_supply`
+    pub fn sell_out_raise(config: CurveConfig, total_supply: u64) -> Result<u128> {
+        let curve = Self::fresh(config, total_supply);
+
+        match curve.config.curve_type {
+            CurveType::ConstantProduct => {
+                // Without virtual tokens the last base unit is infinitely priced, stop one short
+                let sellable = if curve.virtual_token_reserves == 0 {
+                    total_supply.saturating_sub(1)
+                } else {
+                    total_supply
+                };
+                curve.constant_product_buy_price(sellable)
+            }
+            _ => curve.supply_cost(0, total_supply),
+        }
+    }
+
+    /// Whether a fresh curve with `config` that raises exactly its graduation target still
+    /// holds enough tokens to open its pool at its final price
+    pub fn graduation_pool_fits(config: CurveConfig, total_supply: u64) -> bool {
+        let target = config.graduation_sol_target;
+        let mut curve = Self::fresh(config, total_supply);
+
+        let Ok(tokens) = curve.calculate_tokens_for_sol(target) else {
+            return false;
+        };
+        curve.apply_buy(tokens, target).is_ok() && curve.graduation_pool_tokens().is_ok()
+    }
+
+    /// Tokens that pair with the raised SOL so the graduation pool opens at the curve's
+    /// final spot price. Fails when fewer than that are left unsold
+    pub fn graduation_pool_tokens(&self) -> Result<u64> {
+        // real_sol_reserves / token_reserve == spot price
+        let token_reserve = mul_div(
+            self.real_sol_reserves as u128,
+            TOKEN_UNIT * PRICE_SCALE,
+            self.spot_price()? as u128,
+        ).ok_or(error!(ErrorCode::MathOverflow))?;
+        require!(
+            token_reserve <= self.real_token_reserves as u128,
+            ErrorCode::GraduationLiquidityShortfall
+        );
+
+        Ok(token_reserve as u64)
+    }
+
+    /// Whether the curve has raised its graduation target. A curve graduating into the
+    /// program's pool fails instead when the pool couldn't open at its final price
+    pub fn is_complete(&self) -> Result<bool> {
+        if self.real_sol_reserves < self.config.graduation_sol_target {
+            return Ok(false);
+        }
+        if self.graduation_target == GraduationTarget::Pool {
+            self.graduation_pool_tokens()?;
+        }
+
+        Ok(true)
+    }
+
+    /// Pricing only ever uses the tracked reserves, so SOL or tokens donated
+    /// directly to the curve or vault can't move the price
+    pub fn get_effective_amounts(&self) -> Result<(u64, u64)> {
+        let effective_sol = self.real_sol_reserves
+            .checked_add(self.virtual_sol_reserves)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        let effective_tokens = self.real_token_reserves
+            .checked_add(self.virtual_token_reserves)
             .ok_or(error!(ErrorCode::MathOverflow))?;
 
-        Ok((effective_sol, token_vault.amount))
+        Ok((effective_sol, effective_tokens))
     }
 
-    pub fn calculate_buy_price(&self, token_vault: &Account<TokenAccount>, buy_amount: u64, curve_lamports: u64) -> Result<u64> {
-        let (effective_sol, total_tokens) = self.get_effective_amounts(token_vault, curve_lamports)?;
+    /// Tokens bought from the curve and not sold back, in base units
+    pub fn tokens_sold(&self) -> Result<u64> {
+        self.token_total_supply
+            .checked_sub(self.real_token_reserves)
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    /// SOL cost of buying `buy_amount`, rounded in the curve's favor
+    pub fn calculate_buy_price(&self, buy_amount: u64) -> Result<u64> {
+        require!(buy_amount <= self.real_token_reserves, ErrorCode::InsufficientLiquidity);
+
+        let price = match self.config.curve_type {
+            CurveType::ConstantProduct => self.constant_product_buy_price(buy_amount)?,
+            _ => {
+                let sold = self.tokens_sold()?;
+                let new_sold = sold
+                    .checked_add(buy_amount)
+                    .ok_or(error!(ErrorCode::MathOverflow))?;
+                self.supply_cost(sold, new_sold)?
+            }
+        };
+
+        u64::try_from(price).map_err(|_| error!(ErrorCode::MathOverflow))
+    }
+
+    /// SOL returned for selling `sell_amount`, rounded in the curve's favor
+    pub fn calculate_sell_price(&self, sell_amount: u64) -> Result<u64> {
+        let price = match self.config.curve_type {
+            CurveType::ConstantProduct => self.constant_product_sell_price(sell_amount)?,
+            _ => {
+                let sold = self.tokens_sold()?;
+                let new_sold = sold
+                    .checked_sub(sell_amount)
+                    .ok_or(error!(ErrorCode::InsufficientLiquidity))?;
+                self.supply_cost(new_sold, sold)?
+            }
+        };
+
+        // Sellers can never be paid more than buyers put in
+        require!(price <= self.real_sol_reserves as u128, ErrorCode::InsufficientLiquidity);
+
+        Ok(price as u64)
+    }
+
+    /// Tokens received for `sol_amount`, rounded in the curve's favor
+    pub fn calculate_tokens_for_sol(&self, sol_amount: u64) -> Result<u64> {
+        let token_amount = match self.config.curve_type {
+            CurveType::ConstantProduct => self.constant_product_tokens_for_sol(sol_amount)?,
+            CurveType::Linear { .. } => self.linear_tokens_for_sol(sol_amount)?,
+            CurveType::Exponential { base_price, doubling_supply } => {
+                self.exponential_tokens_for_sol(sol_amount, base_price, doubling_supply)?
+            }
+        };
+
+        require!(token_amount <= self.real_token_reserves as u128, ErrorCode::InsufficientLiquidity);
+
+        Ok(token_amount as u64)
+    }
 
-        // Convert to u128 before scaling to prevent precision loss
-        let effective_sol_u128 = effective_sol as u128;
-        let total_tokens_u128 = total_tokens as u128;
+    /// Tokens that must be sold to receive at least `sol_amount`, rounded in the curve's favor
+    pub fn calculate_tokens_for_sol_out(&self, sol_amount: u64) -> Result<u64> {
+        require!(sol_amount <= self.real_sol_reserves, ErrorCode::InsufficientLiquidity);
 
-        // Calculate k = total_tokens * effective_sol with full precision
-        let k = total_tokens_u128
-            .checked_mul(effective_sol_u128)
+        let token_amount = match self.config.curve_type {
+            CurveType::ConstantProduct => self.constant_product_tokens_for_sol_out(sol_amount)?,
+            _ => self.supply_tokens_for_sol_out(sol_amount)?,
+        };
+
+        u64::try_from(token_amount).map_err(|_| error!(ErrorCode::MathOverflow))
+    }
+
+    /// Tokens the developer receives for `sol_amount` at creation, within the protocol's cap
+    pub fn calculate_developer_buy(&self, sol_amount: u64) -> Result<u64> {
+        let token_amount = self.calculate_tokens_for_sol(sol_amount)?;
+
+        let cap = mul_div(self.token_total_supply as u128, MAX_DEVELOPER_BUY_BPS as u128, 10000)
             .ok_or(error!(ErrorCode::MathOverflow))?;
+        require!(token_amount as u128 <= cap, ErrorCode::DeveloperBuyTooLarge);
 
-        // Calculate new token balance with full precision
-        let new_token_amount = total_tokens_u128
-            .checked_sub(buy_amount as u128)
+        Ok(token_amount)
+    }
+
+    /// Current marginal price in lamports per whole token, scaled by PRICE_SCALE
+    pub fn spot_price(&self) -> Result<u64> {
+        let price = match self.config.curve_type {
+            CurveType::ConstantProduct => {
+                let (effective_sol, effective_tokens) = self.get_effective_amounts()?;
+                mul_div(effective_sol as u128, TOKEN_UNIT * PRICE_SCALE, effective_tokens as u128)
+            }
+            CurveType::Linear { base_price, slope } => {
+                let sold = self.tokens_sold()? as u128;
+                let increase = (slope as u128)
+                    .checked_mul(sold)
+                    .and_then(|v| mul_div(v, PRICE_SCALE, TOKEN_UNIT * SLOPE_SCALE));
+                (base_price as u128)
+                    .checked_mul(PRICE_SCALE)
+                    .zip(increase)
+                    .and_then(|(base, increase)| base.checked_add(increase))
+            }
+            CurveType::Exponential { base_price, doubling_supply } => {
+                let growth = self.exponential_growth(self.tokens_sold()?, doubling_supply)?;
+                mul_div((base_price as u128) * PRICE_SCALE, growth, WAD)
+            }
+        }.ok_or(error!(ErrorCode::MathOverflow))?;
+
+        u64::try_from(price).map_err(|_| error!(ErrorCode::MathOverflow))
+    }
+
+    /// Records a buy of `token_amount` for `sol_amount` against the reserves
+    pub fn apply_buy(&mut self, token_amount: u64, sol_amount: u64) -> Result<()> {
+        self.real_token_reserves = self.real_token_reserves
+            .checked_sub(token_amount)
+            .ok_or(error!(ErrorCode::InsufficientLiquidity))?;
+        self.real_sol_reserves = self.real_sol_reserves
+            .checked_add(sol_amount)
             .ok_or(error!(ErrorCode::MathOverflow))?;
 
-        // Calculate new SOL amount maintaining precision
-        let new_sol_amount = k
-            .checked_div(new_token_amount)
+        self.record_trade()
+    }
+
+    /// Records a sell of `token_amount` for `sol_amount` against the reserves
+    pub fn apply_sell(&mut self, token_amount: u64, sol_amount: u64) -> Result<()> {
+        self.real_token_reserves = self.real_token_reserves
+            .checked_add(token_amount)
             .ok_or(error!(ErrorCode::MathOverflow))?;
+        self.real_sol_reserves = self.real_sol_reserves
+            .checked_sub(sol_amount)
+            .ok_or(error!(ErrorCode::InsufficientLiquidity))?;
 
-        // Calculate price difference while maintaining precision
-        let price_difference = new_sol_amount
-            .checked_sub(effective_sol_u128)
+        self.record_trade()
+    }
+
+    fn record_trade(&mut self) -> Result<()> {
+        self.trade_sequence = self.trade_sequence
+            .checked_add(1)
             .ok_or(error!(ErrorCode::MathOverflow))?;
 
-        // Convert back to u64, checking for overflow
-        if price_difference > u64::MAX as u128 {
-            return Err(error!(ErrorCode::MathOverflow));
-        }
-        
-        Ok(price_difference as u64)
+        Ok(())
+    }
+
+    /// Current state of the curve for trade events
+    pub fn trade_snapshot(&self) -> Result<TradeSnapshot> {
+        let clock = Clock::get()?;
+
+        Ok(TradeSnapshot {
+            virtual_sol_reserves: self.virtual_sol_reserves,
+            virtual_token_reserves: self.virtual_token_reserves,
+            real_sol_reserves: self.real_sol_reserves,
+            real_token_reserves: self.real_token_reserves,
+            spot_price: self.spot_price()?,
+            timestamp: clock.unix_timestamp,
+            slot: clock.slot,
+            sequence: self.trade_sequence,
+        })
     }
 
-    pub fn calculate_sell_price(&self, token_vault: &Account<TokenAccount>, sell_amount: u64, curve_lamports: u64) -> Result<u64> {
-        let (effective_sol, total_tokens) = self.get_effective_amounts(token_vault, curve_lamports)?;
+    // Constant product: every division rounds up so k never shrinks, which means a
+    // buy followed by a sell of the same tokens can't return more SOL than was paid
 
-        // Convert to u128 before scaling to prevent precision loss
-        let effective_sol_u128 = effective_sol as u128;
-        let total_tokens_u128 = total_tokens as u128;
-  
+    fn constant_product_buy_price(&self, buy_amount: u64) -> Result<u128> {
+        let (effective_sol, total_tokens) = self.get_effective_amounts()?;
 
-        // Calculate k = total_tokens * effective_sol with full precision
-        let k = total_tokens_u128
-            .checked_mul(effective_sol_u128)
+        let k = (total_tokens as u128)
+            .checked_mul(effective_sol as u128)
             .ok_or(error!(ErrorCode::MathOverflow))?;
 
-        // Calculate new token balance with full precision
-        let new_token_amount = total_tokens_u128
-            .checked_add(sell_amount as u128)
+        let new_token_amount = (total_tokens as u128)
+            .checked_sub(buy_amount as u128)
             .ok_or(error!(ErrorCode::MathOverflow))?;
 
-        // Calculate new SOL amount maintaining precision
-        let new_sol_amount = k
-            .checked_div(new_token_amount)
+        let new_sol_amount = ceil_div(k, new_token_amount)
             .ok_or(error!(ErrorCode::MathOverflow))?;
 
-        // Calculate price difference while maintaining precision
-        let price_difference = effective_sol_u128
-            .checked_sub(new_sol_amount)
+        new_sol_amount
+            .checked_sub(effective_sol as u128)
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    fn constant_product_sell_price(&self, sell_amount: u64) -> Result<u128> {
+        let (effective_sol, total_tokens) = self.get_effective_amounts()?;
+
+        let k = (total_tokens as u128)
+            .checked_mul(effective_sol as u128)
             .ok_or(error!(ErrorCode::MathOverflow))?;
 
-        // Convert back to u64, checking for overflow
-        if price_difference > u64::MAX as u128 {
-            return Err(error!(ErrorCode::MathOverflow));
-        }
-        
-        Ok(price_difference as u64)
+        let new_token_amount = (total_tokens as u128)
+            .checked_add(sell_amount as u128)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        let new_sol_amount = ceil_div(k, new_token_amount)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        (effective_sol as u128)
+            .checked_sub(new_sol_amount)
+            .ok_or(error!(ErrorCode::MathOverflow))
     }
 
-    pub fn calculate_tokens_for_sol(&self, token_vault: &Account<TokenAccount>, sol_amount: u64, curve_lamports: u64) -> Result<u64> {
-        let (effective_sol, total_tokens) = self.get_effective_amounts(token_vault, curve_lamports)?;
-        
-        let effective_sol_u128 = effective_sol as u128;
-        let total_tokens_u128 = total_tokens as u128;
-        let sol_amount_u128 = sol_amount as u128;
+    fn constant_product_tokens_for_sol(&self, sol_amount: u64) -> Result<u128> {
+        let (effective_sol, total_tokens) = self.get_effective_amounts()?;
 
-        // Using x*y=k formula:
         // (effective_sol + sol_amount) * (total_tokens - output_tokens) = effective_sol * total_tokens
-        let k = effective_sol_u128
-            .checked_mul(total_tokens_u128)
+        let k = (effective_sol as u128)
+            .checked_mul(total_tokens as u128)
             .ok_or(error!(ErrorCode::MathOverflow))?;
 
-        let new_sol = effective_sol_u128
-            .checked_add(sol_amount_u128)
+        let new_sol = (effective_sol as u128)
+            .checked_add(sol_amount as u128)
             .ok_or(error!(ErrorCode::MathOverflow))?;
 
-        let new_tokens = k
-            .checked_div(new_sol)
+        let new_tokens = ceil_div(k, new_sol)
             .ok_or(error!(ErrorCode::MathOverflow))?;
 
-        let token_amount = total_tokens_u128
+        (total_tokens as u128)
             .checked_sub(new_tokens)
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    fn constant_product_tokens_for_sol_out(&self, sol_amount: u64) -> Result<u128> {
+        let (effective_sol, total_tokens) = self.get_effective_amounts()?;
+
+        // (effective_sol - sol_amount) * (total_tokens + input_tokens) >= effective_sol * total_tokens
+        let k = (effective_sol as u128)
+            .checked_mul(total_tokens as u128)
             .ok_or(error!(ErrorCode::MathOverflow))?;
 
-        if token_amount > u64::MAX as u128 {
-            return Err(error!(ErrorCode::MathOverflow));
+        let new_sol = (effective_sol as u128)
+            .checked_sub(sol_amount as u128)
+            .filter(|sol| *sol > 0)
+            .ok_or(error!(ErrorCode::InsufficientLiquidity))?;
+
+        let new_tokens = ceil_div(k, new_sol)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        new_tokens
+            .checked_sub(total_tokens as u128)
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    // Linear and exponential curves price against a fixed function of the supply sold.
+    // Costs are differences of the same rounded integral, so any sequence of trades
+    // between two supply levels costs exactly the same and round trips can't profit.
+
+    /// Lamports needed to move the supply sold from `from` to `to`
+    fn supply_cost(&self, from: u64, to: u64) -> Result<u128> {
+        let start = self.supply_integral(from)?;
+        let end = self.supply_integral(to)?;
+
+        end.checked_sub(start).ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    /// Integral of the price from zero supply to `sold` base units, in lamports, rounded down
+    fn supply_integral(&self, sold: u64) -> Result<u128> {
+        let sold = sold as u128;
+
+        match self.config.curve_type {
+            CurveType::Linear { base_price, slope } => {
+                // (2 * a * T * N * x + b * x^2) / (2 * T^2 * N)
+                let scale = TOKEN_UNIT * SLOPE_SCALE;
+                let base = (base_price as u128)
+                    .checked_mul(2 * scale)
+                    .and_then(|v| v.checked_mul(sold))
+                    .ok_or(error!(ErrorCode::MathOverflow))?;
+                let growth = (slope as u128)
+                    .checked_mul(sold)
+                    .and_then(|v| v.checked_mul(sold))
+                    .ok_or(error!(ErrorCode::MathOverflow))?;
+
+                base.checked_add(growth)
+                    .map(|v| v / (2 * scale * TOKEN_UNIT))
+                    .ok_or(error!(ErrorCode::MathOverflow))
+            }
+            CurveType::Exponential { base_price, doubling_supply } => {
+                // a * D / ln2 * 2^(x / D)
+                let growth = self.exponential_growth(sold as u64, doubling_supply)?;
+                (base_price as u128)
+                    .checked_mul(doubling_supply as u128)
+                    .and_then(|scale| mul_div(scale, growth, LN2_WAD))
+                    .ok_or(error!(ErrorCode::MathOverflow))
+            }
+            CurveType::ConstantProduct => Err(error!(ErrorCode::InvalidCurveConfig)),
         }
+    }
 
-        Ok(token_amount as u64)
+    /// 2^(sold / doubling_supply) scaled by WAD
+    fn exponential_growth(&self, sold: u64, doubling_supply: u64) -> Result<u128> {
+        let exponent = mul_div(sold as u128, WAD, (doubling_supply as u128) * TOKEN_UNIT)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        exp2_wad(exponent).ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    /// Budget for a buy of `sol_amount`, as a value of the supply integral. Fails if the
+    /// SOL would buy more than the tokens left, rather than charging for tokens the buyer
+    /// can't receive
+    fn supply_budget(&self, sold: u64, sol_amount: u64) -> Result<u128> {
+        let budget = self.supply_integral(sold)?
+            .checked_add(sol_amount as u128)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        let sold_out = sold
+            .checked_add(self.real_token_reserves)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        require!(budget <= self.supply_integral(sold_out)?, ErrorCode::InsufficientLiquidity);
+
+        Ok(budget)
+    }
+
+    /// Largest amount whose cost doesn't exceed `sol_amount`, found by bisection
+    fn linear_tokens_for_sol(&self, sol_amount: u64) -> Result<u128> {
+        let sold = self.tokens_sold()?;
+        let budget = self.supply_budget(sold, sol_amount)?;
+
+        self.largest_amount_within(sold, budget, self.real_token_reserves)
+            .map(u128::from)
+    }
+
+    /// Largest amount up to `high` whose supply integral stays within `budget`
+    fn largest_amount_within(&self, sold: u64, budget: u128, high: u64) -> Result<u64> {
+        let (mut low, mut high) = (0u64, high);
+        while low < high {
+            let mid = low + (high - low + 1) / 2;
+            if self.supply_integral(sold + mid)? <= budget {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+
+        Ok(low)
+    }
+
+    /// Smallest amount whose sale returns at least `sol_amount`, found by bisection
+    fn supply_tokens_for_sol_out(&self, sol_amount: u64) -> Result<u128> {
+        let sold = self.tokens_sold()?;
+        let floor = self.supply_integral(sold)?
+            .checked_sub(sol_amount as u128)
+            .ok_or(error!(ErrorCode::InsufficientLiquidity))?;
+
+        // Even selling everything back can't reach the requested amount
+        require!(self.supply_integral(0)? <= floor, ErrorCode::InsufficientLiquidity);
+
+        let (mut low, mut high) = (0u64, sold);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.supply_integral(sold - mid)? <= floor {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        Ok(low as u128)
+    }
+
+    /// Inverts the integral with log2. Rounding can overshoot the estimate by a unit, so
+    /// it's corrected by one step, with a bisection below it as a bounded fallback
+    fn exponential_tokens_for_sol(&self, sol_amount: u64, base_price: u64, doubling_supply: u64) -> Result<u128> {
+        let sold = self.tokens_sold()?;
+        let budget = self.supply_budget(sold, sol_amount)?;
+
+        let estimate = self.exponential_estimate(sold, budget, base_price, doubling_supply)?;
+        if estimate == 0 || self.supply_integral(sold + estimate)? <= budget {
+            return Ok(estimate as u128);
+        }
+
+        let corrected = estimate - 1;
+        if self.supply_integral(sold + corrected)? <= budget {
+            return Ok(corrected as u128);
+        }
+
+        self.largest_amount_within(sold, budget, corrected)
+            .map(u128::from)
+    }
+
+    /// Amount at which the exponential integral reaches `budget`, clamped to the reserves
+    fn exponential_estimate(&self, sold: u64, budget: u128, base_price: u64, doubling_supply: u64) -> Result<u64> {
+        let scale = (base_price as u128) * (doubling_supply as u128);
+        // Rounding can leave the growth a hair under 2^0, clamp it
+        let growth = mul_div(LN2_WAD, budget, scale)
+            .ok_or(error!(ErrorCode::MathOverflow))?
+            .max(WAD);
+        let exponent = log2_wad(growth).ok_or(error!(ErrorCode::MathOverflow))?;
+        let new_sold = mul_div(exponent, (doubling_supply as u128) * TOKEN_UNIT, WAD)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        Ok(new_sold.saturating_sub(sold as u128).min(self.real_token_reserves as u128) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::curve_config::LaunchTax;
+    use crate::state::test_utils::{curve, SOL, SUPPLY};
+    use crate::utils::math::MAX_EXP2_EXPONENT;
+
+    fn all_curves() -> [BondingCurve; 3] {
+        [
+            curve(CurveType::ConstantProduct),
+            curve(CurveType::Linear { base_price: 10, slope: 100 }),
+            curve(CurveType::Exponential { base_price: 10, doubling_supply: 200_000_000 }),
+        ]
+    }
+
+    const AMOUNTS: [u64; 5] = [1, 999, 1_000_000, 12_345_678_901, 100_000_000 * TOKEN_UNIT as u64];
+
+    #[test]
+    fn buy_then_sell_never_profits() {
+        for mut curve in all_curves() {
+            for amount in AMOUNTS {
+                let cost = curve.calculate_buy_price(amount).unwrap();
+                curve.apply_buy(amount, cost).unwrap();
+
+                let proceeds = curve.calculate_sell_price(amount).unwrap();
+                assert!(proceeds <= cost, "{amount}: sold for {proceeds}, paid {cost}");
+                curve.apply_sell(amount, proceeds).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn buy_with_sol_then_sell_never_profits() {
+        for mut curve in all_curves() {
+            for sol_amount in [1, 1_000, SOL / 3, 2 * SOL + 7] {
+                let tokens = curve.calculate_tokens_for_sol(sol_amount).unwrap();
+                curve.apply_buy(tokens, sol_amount).unwrap();
+
+                let proceeds = curve.calculate_sell_price(tokens).unwrap();
+                assert!(proceeds <= sol_amount, "sold for {proceeds}, paid {sol_amount}");
+                curve.apply_sell(tokens, proceeds).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn tokens_for_sol_never_exceed_what_the_sol_buys() {
+        for mut curve in all_curves() {
+            curve.apply_buy(50_000_000 * TOKEN_UNIT as u64, 0).unwrap();
+            for sol_amount in [1, 777, SOL, 5 * SOL + 1] {
+                let tokens = curve.calculate_tokens_for_sol(sol_amount).unwrap();
+                assert!(curve.calculate_buy_price(tokens).unwrap() <= sol_amount);
+            }
+        }
+    }
+
+    #[test]
+    fn tokens_for_sol_out_always_cover_the_payout() {
+        for mut curve in all_curves() {
+            let bought = 200_000_000 * TOKEN_UNIT as u64;
+            let cost = curve.calculate_buy_price(bought).unwrap();
+            curve.apply_buy(bought, cost).unwrap();
+
+            for sol_out in [1, 999, SOL / 7, cost / 2] {
+                let tokens = curve.calculate_tokens_for_sol_out(sol_out).unwrap();
+                assert!(curve.calculate_sell_price(tokens).unwrap() >= sol_out);
+            }
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn buys_past_the_remaining_supply_fail() {
+        for mut curve in all_curves() {
+            let left = 1_000 * TOKEN_UNIT as u64;
+            curve.apply_buy(SUPPLY - left, 0).unwrap();
+
+            let sold_out_cost = curve.calculate_buy_price(left).unwrap();
+            assert_eq!(
+                curve.calculate_buy_price(left + 1).unwrap_err(),
+                error!(ErrorCode::InsufficientLiquidity),
+            );
+            assert_eq!(
+                curve.calculate_tokens_for_sol(sold_out_cost.saturating_mul(1_000)).unwrap_err(),
+                error!(ErrorCode::InsufficientLiquidity),
+            );
+        }
+    }
+
+    #[test]
+    fn supply_curves_can_buy_up_to_the_remaining_supply() {
+        for mut curve in all_curves().into_iter().skip(1) {
+            let left = 1_000 * TOKEN_UNIT as u64;
+            curve.apply_buy(SUPPLY - left, 0).unwrap();
+
+            // Inverting the sell-out cost rounds down, never past the supply
+            let cost = curve.calculate_buy_price(left).unwrap();
+            let tokens = curve.calculate_tokens_for_sol(cost).unwrap();
+            assert!(tokens <= left);
+            assert!(tokens >= left - left / 1_000);
+        }
+    }
+
+    #[test]
+    fn launch_tax_decays_over_the_slots_since_creation() {
+        let mut curve = curve(CurveType::ConstantProduct);
+        curve.created_slot = 1_000;
+        curve.config.launch_tax = Some(LaunchTax { start_bps: 2_100, decay_slots: 600 });
+
+        // Full rate less the trade fee at the start, half way down at the midpoint
+        assert_eq!(curve.launch_tax_bps(100, 1_000), 2_000);
+        assert_eq!(curve.launch_tax_bps(100, 1_300), 1_000);
+        assert_eq!(curve.launch_tax_bps(100, 1_599), 3);
+        assert_eq!(curve.launch_tax_bps(100, 1_600), 0);
+        assert_eq!(curve.launch_tax_bps(100, 5_000), 0);
+
+        // A delayed trading start doesn't stretch the window
+        curve.config.trading_starts_at = i64::MAX;
+        assert_eq!(curve.launch_tax_bps(100, 1_300), 1_000);
+    }
+
+    #[test]
+    fn validate_rejects_unreachable_graduation_targets() {
+        let linear = CurveConfig {
+            curve_type: CurveType::Linear { base_price: 1, slope: 0 },
+            virtual_sol_reserves: 30 * SOL,
+            graduation_sol_target: 20 * SOL,
+            ..Default::default()
+        };
+        // Selling out raises 1 SOL
+        assert!(!linear.validate(SUPPLY));
+
+        let constant_product = CurveConfig {
+            virtual_sol_reserves: 30 * SOL,
+            virtual_token_reserves: 100 * SUPPLY,
+            graduation_sol_target: 20 * SOL,
+            ..Default::default()
+        };
+        assert!(!constant_product.validate(SUPPLY));
+    }
+
+    #[test]
+    fn validate_rejects_curves_too_short_of_tokens_to_open_their_pool() {
+        let config = CurveConfig {
+            virtual_sol_reserves: 30 * SOL,
+            virtual_token_reserves: 1_073_000_000 * TOKEN_UNIT as u64,
+            graduation_sol_target: 20 * SOL,
+            ..Default::default()
+        };
+        // Selling out raises plenty, but at 20 SOL only ~171M tokens are left against the
+        // ~497M the pool needs
+        assert!(BondingCurve::sell_out_raise(config.clone(), SUPPLY).unwrap() >= 20 * SOL as u128);
+        assert!(!config.validate(SUPPLY));
+    }
+
+    #[test]
+    fn graduation_pool_opens_at_the_curve_spot_price() {
+        for mut curve in all_curves() {
+            let target = curve.config.graduation_sol_target;
+            let tokens = curve.calculate_tokens_for_sol(target).unwrap();
+            curve.apply_buy(tokens, target).unwrap();
+
+            let spot_price = curve.spot_price().unwrap() as u128;
+            let pool_tokens = curve.graduation_pool_tokens().unwrap();
+            assert!(pool_tokens <= curve.real_token_reserves);
+
+            let pool_price = mul_div(target as u128, TOKEN_UNIT * PRICE_SCALE, pool_tokens as u128).unwrap();
+            assert!(pool_price.abs_diff(spot_price) * 1_000_000 <= spot_price, "{pool_price} vs {spot_price}");
+        }
+    }
+
+    #[test]
+    fn graduation_fails_once_too_few_tokens_are_left() {
+        for mut curve in all_curves() {
+            let left = curve.real_token_reserves / 1_000;
+            let cost = curve.calculate_buy_price(curve.real_token_reserves - left).unwrap();
+            curve.apply_buy(curve.real_token_reserves - left, cost).unwrap();
+
+            assert_eq!(
+                curve.graduation_pool_tokens().unwrap_err(),
+                error!(ErrorCode::GraduationLiquidityShortfall),
+            );
+        }
+    }
+
+    #[test]
+    fn validate_rejects_exponential_curves_past_the_doubling_limit() {
+        let exponential = |doubling_supply: u64| CurveConfig {
+            curve_type: CurveType::Exponential { base_price: 1, doubling_supply },
+            virtual_sol_reserves: 30 * SOL,
+            graduation_sol_target: 20 * SOL,
+            ..Default::default()
+        };
+
+        let whole_supply = SUPPLY / TOKEN_UNIT as u64;
+        assert!(exponential(whole_supply / MAX_EXP2_EXPONENT as u64).validate(SUPPLY));
+        assert!(!exponential(whole_supply / (MAX_EXP2_EXPONENT as u64 + 1)).validate(SUPPLY));
+    }
+
+    #[test]
+    fn exponential_estimate_needs_at_most_one_correction_at_max_supply() {
+        let whole_supply = SUPPLY / TOKEN_UNIT as u64;
+        let steepest = (1, whole_supply / MAX_EXP2_EXPONENT as u64);
+
+        for (base_price, doubling_supply) in [steepest, (10, 200_000_000)] {
+            let mut curve = curve(CurveType::Exponential { base_price, doubling_supply });
+            for bought in [0, SUPPLY / 4, SUPPLY / 2, SUPPLY - SUPPLY / 100] {
+                curve.apply_buy(bought - curve.tokens_sold().unwrap(), 0).unwrap();
+                let sold = curve.tokens_sold().unwrap();
+
+                for sol_amount in [1, 999, SOL, 1_000 * SOL, u64::MAX] {
+                    // Buys past the remaining reserves are rejected before the estimate
+                    let Ok(budget) = curve.supply_budget(sold, sol_amount) else { continue };
+                    let estimate = curve
+                        .exponential_estimate(sold, budget, base_price, doubling_supply)
+                        .unwrap();
+
+                    let corrected = estimate.saturating_sub(1);
+                    assert!(curve.supply_integral(sold + corrected).unwrap() <= budget, "{sold} {sol_amount}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::utils::error::ErrorCode;
+use crate::state::fee_vault::FeeVault;
+
+/// Holds the creator's share of a curve's trade fees until the developer claims it
+#[account]
+pub struct CreatorFeeVault {
+    pub curve: Pubkey,
+    pub creator: Pubkey,
+    /// Lamports credited to the vault over the curve's lifetime
+    pub total_accrued: u64,
+    /// Lamports paid out to the creator so far
+    pub total_claimed: u64,
+    pub bump: u8,
+}
+
+impl CreatorFeeVault {
+    pub fn initialize(&mut self, curve: Pubkey, creator: Pubkey, bump: u8) {
+        self.curve = curve;
+        self.creator = creator;
+        self.total_accrued = 0;
+        self.total_claimed = 0;
+        self.bump = bump;
+    }
+
+    pub fn accrue(&mut self, amount: u64) -> Result<()> {
+        self.total_accrued = self.total_accrued
+            .checked_add(amount)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        Ok(())
+    }
+}
+
+impl FeeVault for CreatorFeeVault {
+    fn total_accrued(&self) -> u64 {
+        self.total_accrued
+    }
+
+    fn total_claimed(&self) -> u64 {
+        self.total_claimed
+    }
+
+    fn set_total_claimed(&mut self, total_claimed: u64) {
+        self.total_claimed = total_claimed;
+    }
+}
+
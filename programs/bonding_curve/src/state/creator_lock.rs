@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use crate::utils::error::ErrorCode;
+
+/// Vesting terms for a developer's initial buy, relative to the token's creation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct LockSchedule {
+    /// Nothing can be claimed before the cliff
+    pub cliff_seconds: i64,
+    /// Everything is claimable once the full duration has passed
+    pub duration_seconds: i64,
+}
+
+impl LockSchedule {
+    pub fn validate(&self) -> bool {
+        self.duration_seconds > 0 && (0..=self.duration_seconds).contains(&self.cliff_seconds)
+    }
+}
+
+/// Holds the developer's initial-buy tokens, released linearly after a cliff
+#[account]
+pub struct CreatorLock {
+    pub curve: Pubkey,
+    pub mint: Pubkey,
+    pub beneficiary: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_ts: i64,
+    pub cliff_ts: i64,
+    pub end_ts: i64,
+    pub bump: u8,
+}
+
+impl CreatorLock {
+    /// Starts the schedule from the current time
+    pub fn initialize(
+        &mut self,
+        curve: Pubkey,
+        mint: Pubkey,
+        beneficiary: Pubkey,
+        total_amount: u64,
+        schedule: LockSchedule,
+        bump: u8,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        self.curve = curve;
+        self.mint = mint;
+        self.beneficiary = beneficiary;
+        self.total_amount = total_amount;
+        self.claimed_amount = 0;
+        self.start_ts = now;
+        self.cliff_ts = now
+            .checked_add(schedule.cliff_seconds)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        self.end_ts = now
+            .checked_add(schedule.duration_seconds)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        self.bump = bump;
+
+        Ok(())
+    }
+
+    /// Tokens vested by `now`, claimed or not
+    pub fn vested_amount(&self, now: i64) -> Result<u64> {
+        if now < self.cliff_ts {
+            return Ok(0);
+        }
+        if now >= self.end_ts {
+            return Ok(self.total_amount);
+        }
+
+        let elapsed = (now - self.start_ts) as u128;
+        let duration = (self.end_ts - self.start_ts) as u128;
+        let vested = (self.total_amount as u128)
+            .checked_mul(elapsed)
+            .ok_or(error!(ErrorCode::MathOverflow))?
+            / duration;
+
+        Ok(vested as u64)
+    }
+
+    /// Tokens vested but not yet claimed
+    pub fn claimable_amount(&self, now: i64) -> Result<u64> {
+        self.vested_amount(now)?
+            .checked_sub(self.claimed_amount)
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    /// Tokens still waiting to vest
+    pub fn locked_amount(&self, now: i64) -> Result<u64> {
+        self.total_amount
+            .checked_sub(self.vested_amount(now)?)
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1_000 tokens from t=100, cliff at t=200 and fully vested at t=500
+    fn lock() -> CreatorLock {
+        CreatorLock {
+            curve: Pubkey::default(),
+            mint: Pubkey::default(),
+            beneficiary: Pubkey::default(),
+            total_amount: 1_000,
+            claimed_amount: 0,
+            start_ts: 100,
+            cliff_ts: 200,
+            end_ts: 500,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn nothing_vests_before_the_cliff() {
+        let lock = lock();
+
+        assert_eq!(lock.vested_amount(100).unwrap(), 0);
+        assert_eq!(lock.vested_amount(199).unwrap(), 0);
+        assert_eq!(lock.locked_amount(199).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn vesting_is_linear_from_the_start_once_past_the_cliff() {
+        let mut lock = lock();
+
+        assert_eq!(lock.vested_amount(200).unwrap(), 250);
+        assert_eq!(lock.vested_amount(300).unwrap(), 500);
+        assert_eq!(lock.locked_amount(300).unwrap(), 500);
+
+        lock.claimed_amount = 250;
+        assert_eq!(lock.claimable_amount(300).unwrap(), 250);
+    }
+
+    #[test]
+    fn everything_vests_at_the_end() {
+        let mut lock = lock();
+
+        assert_eq!(lock.vested_amount(500).unwrap(), 1_000);
+        assert_eq!(lock.vested_amount(i64::MAX).unwrap(), 1_000);
+        assert_eq!(lock.locked_amount(500).unwrap(), 0);
+
+        lock.claimed_amount = 1_000;
+        assert_eq!(lock.claimable_amount(600).unwrap(), 0);
+    }
+
+    #[test]
+    fn schedules_need_a_duration_and_a_cliff_within_it() {
+        let schedule = |cliff_seconds, duration_seconds| LockSchedule { cliff_seconds, duration_seconds };
+
+        assert!(schedule(0, 1).validate());
+        assert!(schedule(10, 10).validate());
+        assert!(!schedule(0, 0).validate());
+        assert!(!schedule(11, 10).validate());
+        assert!(!schedule(-1, 10).validate());
+    }
+}
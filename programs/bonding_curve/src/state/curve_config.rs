@@ -1,25 +1,172 @@
 use anchor_lang::prelude::*;
 use serde::{Serialize, Deserialize};
 use solana_program::pubkey::Pubkey;
+use crate::state::bonding_curve::BondingCurve;
+use crate::state::global_config::GlobalConfig;
+use crate::utils::error::ErrorCode;
+use crate::utils::constants::{
+    MIN_VIRTUAL_SOL_RESERVES,
+    MAX_VIRTUAL_SOL_RESERVES,
+    MAX_VIRTUAL_TOKEN_RESERVES,
+    MIN_GRADUATION_SOL_TARGET,
+    MAX_GRADUATION_SOL_TARGET,
+    MAX_ALLOWLIST_DURATION_SECONDS,
+    MAX_LAUNCH_TAX_BPS,
+    MAX_LAUNCH_TAX_DECAY_SLOTS,
+    TOKEN_UNIT,
+};
+use crate::utils::math::MAX_EXP2_EXPONENT;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
 pub enum MigrationStatus {
     #[default]
     Active,
-    Migrated
+    Migrated,
+    /// Threshold reached, trading halted until the curve is graduated into its pool
+    Graduating,
+}
+
+/// Price curve chosen by the creator at launch. Prices are in lamports per whole token.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum CurveType {
+    /// x*y=k over the real plus virtual reserves
+    #[default]
+    ConstantProduct,
+    /// price = base_price + slope * tokens_sold / SLOPE_SCALE, with tokens_sold in whole tokens
+    Linear { base_price: u64, slope: u64 },
+    /// price = base_price * 2^(tokens_sold / doubling_supply), with tokens_sold in whole tokens
+    Exponential { base_price: u64, doubling_supply: u64 },
+}
+
+/// Window after trading opens during which only allowlisted wallets may buy
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct AllowlistPhase {
+    /// Root of a Merkle tree over keccak hashes of the allowlisted wallets
+    pub merkle_root: [u8; 32],
+    /// Unix timestamp at which buying opens to everyone
+    pub ends_at: i64,
+    /// Most tokens one wallet may buy during the phase
+    pub max_tokens_per_wallet: u64,
+}
+
+/// Extra tax on early buys that decays to nothing after launch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct LaunchTax {
+    /// Total buy rate at creation, including the trade fee
+    pub start_bps: u64,
+    /// Slots over which the rate falls back to the trade fee
+    pub decay_slots: u64,
 }
 
 #[account]
 #[derive(Default, Serialize, Deserialize)]
 pub struct CurveConfig {
     pub migration_status: MigrationStatus,
-    pub is_subscribed: bool,
     pub developer: Pubkey,
+    pub curve_type: CurveType,
+    /// Virtual SOL for constant-product pricing, defaults to the protocol setting when zero
+    pub virtual_sol_reserves: u64,
+    /// Virtual tokens for constant-product pricing
+    pub virtual_token_reserves: u64,
+    /// Real SOL the curve must raise before it graduates, defaults to the protocol setting when zero
+    pub graduation_sol_target: u64,
+    /// Unix timestamp before which the curve can't be bought from
+    pub trading_starts_at: i64,
+    pub allowlist: Option<AllowlistPhase>,
+    pub launch_tax: Option<LaunchTax>,
+    /// Most tokens one wallet may hold while the curve is trading
+    pub max_tokens_per_wallet: Option<u64>,
+    /// Most tokens one buy may take while the curve is trading
+    pub max_tokens_per_tx: Option<u64>,
 }
 
 impl CurveConfig {
-    pub fn validate(&self) -> bool {
-        true // No need to validate virtual_sol anymore
+    /// Fills in unset values from the protocol defaults
+    pub fn apply_defaults(&mut self, global_config: &GlobalConfig) {
+        if self.virtual_sol_reserves == 0 {
+            self.virtual_sol_reserves = global_config.virtual_sol_amount;
+        }
+        if self.graduation_sol_target == 0 {
+            self.graduation_sol_target = global_config.migration_threshold;
+        }
+    }
+
+    /// Fills in the defaults, checks the result and hands the curve to `developer`.
+    /// Every launch path prepares its settings here before initializing the curve
+    pub fn prepare(&mut self, global_config: &GlobalConfig, developer: Pubkey, total_supply: u64) -> Result<()> {
+        self.apply_defaults(global_config);
+        require!(self.validate(total_supply), ErrorCode::InvalidCurveConfig);
+        self.developer = developer;
+
+        Ok(())
+    }
+
+    /// Checks a buy of `amount` that leaves the buyer holding `balance_after`. The caps
+    /// only apply until the curve has migrated
+    pub fn check_buy_limits(&self, amount: u64, balance_after: u64) -> Result<()> {
+        if self.migration_status == MigrationStatus::Migrated {
+            return Ok(());
+        }
+
+        if let Some(max_tokens_per_tx) = self.max_tokens_per_tx {
+            require!(amount <= max_tokens_per_tx, ErrorCode::TxCapExceeded);
+        }
+        if let Some(max_tokens_per_wallet) = self.max_tokens_per_wallet {
+            require!(balance_after <= max_tokens_per_wallet, ErrorCode::WalletCapExceeded);
+        }
+
+        Ok(())
+    }
+
+    /// Checks the settings for a curve selling `total_supply`
+    pub fn validate(&self, total_supply: u64) -> bool {
+        if !(MIN_VIRTUAL_SOL_RESERVES..=MAX_VIRTUAL_SOL_RESERVES).contains(&self.virtual_sol_reserves) {
+            return false;
+        }
+
+        if self.virtual_token_reserves > MAX_VIRTUAL_TOKEN_RESERVES {
+            return false;
+        }
+
+        if !(MIN_GRADUATION_SOL_TARGET..=MAX_GRADUATION_SOL_TARGET).contains(&self.graduation_sol_target) {
+            return false;
+        }
+
+        if let Some(allowlist) = self.allowlist {
+            let duration = allowlist.ends_at.saturating_sub(self.trading_starts_at);
+            if !(1..=MAX_ALLOWLIST_DURATION_SECONDS).contains(&duration) || allowlist.max_tokens_per_wallet == 0 {
+                return false;
+            }
+        }
+
+        if let Some(tax) = self.launch_tax {
+            if tax.start_bps > MAX_LAUNCH_TAX_BPS || !(1..=MAX_LAUNCH_TAX_DECAY_SLOTS).contains(&tax.decay_slots) {
+                return false;
+            }
+        }
+
+        if self.max_tokens_per_wallet == Some(0) || self.max_tokens_per_tx == Some(0) {
+            return false;
+        }
+
+        let shape_valid = match self.curve_type {
+            CurveType::ConstantProduct => true,
+            CurveType::Linear { base_price, slope } => base_price > 0 || slope > 0,
+            CurveType::Exponential { base_price, doubling_supply } => {
+                // Past the last doubling exp2_wad overflows and buys would start failing
+                let doublings = total_supply as u128 / ((doubling_supply as u128) * TOKEN_UNIT).max(1);
+                base_price > 0 && doubling_supply > 0 && doublings <= MAX_EXP2_EXPONENT
+            }
+        };
+        if !shape_valid {
+            return false;
+        }
+
+        // A curve that can't raise its target even by selling out would never graduate
+        let reachable = BondingCurve::sell_out_raise(self.clone(), total_supply)
+            .is_ok_and(|raise| raise >= self.graduation_sol_target as u128);
+
+        reachable && BondingCurve::graduation_pool_fits(self.clone(), total_supply)
     }
 }
 
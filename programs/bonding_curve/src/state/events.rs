@@ -1,30 +1,300 @@
 use anchor_lang::prelude::*;
+use crate::state::curve_config::CurveConfig;
 
 #[event]
 pub struct MigrationEvent {
     pub mint: Pubkey,
+    pub pool: Pubkey,
     pub real_sol_amount: u64,
     pub virtual_sol_amount: u64,
     pub token_amount: u64,
+    /// Vault tokens not needed to match the curve's final price
+    pub tokens_burned: u64,
+    /// Lamports per whole token
     pub effective_price: u64,
     pub developer: Pubkey,
-    pub is_subscribed: bool,
+    /// Read from the developer's `Subscription` account at graduation
+    pub developer_subscribed: bool,
+    /// Developer tokens still waiting to vest in the creator lock
+    pub developer_tokens_locked: u64,
+}
+
+#[event]
+pub struct CurveCompletedEvent {
+    pub mint: Pubkey,
+    pub curve: Pubkey,
+    pub real_sol_amount: u64,
+}
+
+/// Curve state right after a trade, so indexers don't need to fetch the account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TradeSnapshot {
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    /// Spot price in lamports per whole token, scaled by PRICE_SCALE
+    pub spot_price: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+    /// Position of the trade in the curve's history, starting at 1
+    pub sequence: u64,
 }
 
 #[event]
 pub struct BuyEvent {
+    /// Layout version, see TRADE_EVENT_VERSION
+    pub version: u8,
     pub mint: Pubkey,
     pub amount: u64,
     pub sol_amount: u64,
     pub buyer: Pubkey,
     pub is_subscribed: bool,
+    pub referrer: Option<Pubkey>,
+    pub referral_fee: u64,
+    /// Bought by the developer while creating the token
+    pub is_developer_buy: bool,
+    /// Launch tax charged on top of the trade fee, in bps. It's kept in the curve
+    pub launch_tax_bps: u64,
+    pub launch_tax: u64,
+    /// Trade fee paid, including the creator's and referrer's shares
+    pub fee: u64,
+    pub snapshot: TradeSnapshot,
 }
 
 #[event]
 pub struct SellEvent {
+    /// Layout version, see TRADE_EVENT_VERSION
+    pub version: u8,
     pub mint: Pubkey,
     pub amount: u64,
     pub sol_amount: u64,
     pub seller: Pubkey,
     pub is_subscribed: bool,
-} 
\ No newline at end of file
+    pub referrer: Option<Pubkey>,
+    pub referral_fee: u64,
+    /// Trade fee paid, including the creator's and referrer's shares
+    pub fee: u64,
+    pub snapshot: TradeSnapshot,
+}
+
+#[event]
+pub struct SubscriptionPurchasedEvent {
+    pub user: Pubkey,
+    pub periods: u16,
+    pub amount: u64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct AllowlistJoinedEvent {
+    pub mint: Pubkey,
+    pub curve: Pubkey,
+    pub wallet: Pubkey,
+    pub allowlist_entry: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum AuthorityKind {
+    Admin,
+    FeeCollector,
+    MigrationAdmin,
+}
+
+#[event]
+pub struct AuthorityChangedEvent {
+    pub kind: AuthorityKind,
+    pub previous: Pubkey,
+    pub new: Pubkey,
+}
+
+#[event]
+pub struct AdminProposedEvent {
+    pub admin: Pubkey,
+    /// None when a pending proposal is cancelled
+    pub pending_admin: Option<Pubkey>,
+}
+
+#[event]
+pub struct SwapEvent {
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub is_buy: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub sol_reserve: u64,
+    pub token_reserve: u64,
+}
+
+#[event]
+pub struct LiquidityAddedEvent {
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+    pub provider: Pubkey,
+    pub lp_amount: u64,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+}
+
+#[event]
+pub struct LiquidityRemovedEvent {
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+    pub provider: Pubkey,
+    pub lp_amount: u64,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+}
+
+#[event]
+pub struct MigrationEscrowedEvent {
+    pub mint: Pubkey,
+    pub escrow: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub circulating_supply: u64,
+    pub deadline_slot: u64,
+    pub developer: Pubkey,
+    /// Developer tokens still waiting to vest in the creator lock
+    pub developer_tokens_locked: u64,
+}
+
+#[event]
+pub struct MigrationCompletedEvent {
+    pub mint: Pubkey,
+    pub escrow: Pubkey,
+    pub migration_admin: Pubkey,
+    pub destination_pool: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+}
+
+#[event]
+pub struct ReclaimEvent {
+    pub mint: Pubkey,
+    pub escrow: Pubkey,
+    pub holder: Pubkey,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+}
+
+#[event]
+pub struct ExcessSweptEvent {
+    pub mint: Pubkey,
+    pub curve: Pubkey,
+    pub sol_amount: u64,
+    /// Donated vault tokens, burned
+    pub token_amount: u64,
+}
+
+#[event]
+pub struct CreatorFeeAccruedEvent {
+    pub mint: Pubkey,
+    pub curve: Pubkey,
+    pub amount: u64,
+    pub total_accrued: u64,
+}
+
+#[event]
+pub struct CreatorFeesClaimedEvent {
+    pub mint: Pubkey,
+    pub curve: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+}
+
+#[event]
+pub struct ReferrerRegisteredEvent {
+    pub referrer: Pubkey,
+    pub referral_vault: Pubkey,
+}
+
+#[event]
+pub struct ReferrerSetEvent {
+    pub trader: Pubkey,
+    pub referrer: Pubkey,
+}
+
+#[event]
+pub struct ReferralFeeAccruedEvent {
+    pub mint: Pubkey,
+    pub referrer: Pubkey,
+    pub referral_vault: Pubkey,
+    pub amount: u64,
+    pub total_accrued: u64,
+}
+
+#[event]
+pub struct ReferralFeesClaimedEvent {
+    pub referrer: Pubkey,
+    pub referral_vault: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+}
+
+#[event]
+pub struct CreatorLockCreatedEvent {
+    pub mint: Pubkey,
+    pub curve: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub cliff_ts: i64,
+    pub end_ts: i64,
+}
+
+#[event]
+pub struct VestedClaimedEvent {
+    pub mint: Pubkey,
+    pub curve: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub remaining_locked: u64,
+}
+
+#[event]
+pub struct MetadataUpdatedEvent {
+    pub mint: Pubkey,
+    pub curve: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+#[event]
+pub struct CollectionInitializedEvent {
+    pub collection_mint: Pubkey,
+    pub collection_metadata: Pubkey,
+    pub collection_authority: Pubkey,
+}
+
+#[event]
+pub struct TokenGroupInitializedEvent {
+    pub group_mint: Pubkey,
+    pub group_authority: Pubkey,
+}
+
+#[event]
+pub struct TokenCreatedEvent {
+    pub mint: Pubkey,
+    pub curve: Pubkey,
+    pub developer: Pubkey,
+    pub token_seed: String,
+    pub total_supply: u64,
+    pub curve_config: CurveConfig,
+    /// Token program the mint belongs to, classic SPL Token or Token-2022
+    pub token_program: Pubkey,
+}
+
+#[event]
+pub struct MetadataCreatedEvent {
+    pub mint: Pubkey,
+    pub curve: Pubkey,
+    /// Metaplex metadata account, or the mint itself for Token-2022 metadata
+    pub metadata: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::utils::error::ErrorCode;
+
+/// Program-owned vault holding accrued fee lamports on top of its rent until they're claimed
+pub trait FeeVault {
+    fn total_accrued(&self) -> u64;
+    fn total_claimed(&self) -> u64;
+    fn set_total_claimed(&mut self, total_claimed: u64);
+
+    /// Fees accrued but not yet claimed
+    fn claimable(&self) -> Result<u64> {
+        self.total_accrued()
+            .checked_sub(self.total_claimed())
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+}
+
+/// Pays out everything accrued to the vault since the last claim and returns the amount
+pub fn withdraw_fees<'info, T>(vault: &mut Account<'info, T>, recipient: &AccountInfo<'info>) -> Result<u64>
+where
+    T: FeeVault + AccountSerialize + AccountDeserialize + Owner + Clone,
+{
+    let amount = vault.claimable()?;
+    require!(amount > 0, ErrorCode::NothingToClaim);
+
+    let vault_info = vault.to_account_info();
+    let vault_lamports = vault_info.lamports();
+    let recipient_lamports = recipient.lamports();
+
+    **vault_info.try_borrow_mut_lamports()? = vault_lamports
+        .checked_sub(amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    **recipient.try_borrow_mut_lamports()? = recipient_lamports
+        .checked_add(amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+
+    let total_claimed = vault.total_claimed()
+        .checked_add(amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    vault.set_total_claimed(total_claimed);
+
+    Ok(amount)
+}
@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use crate::utils::error::ErrorCode;
+use crate::utils::math::{ceil_div, mul_div};
+
+/// Where completed curves send their liquidity
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default)]
+pub enum GraduationTarget {
+    /// Program-owned constant-product pool
+    #[default]
+    Pool,
+    /// Timelocked escrow released to the migration admin for an external pool
+    Escrow,
+}
+
+/// Protocol-wide settings shared by every curve, so devnet and mainnet can run
+/// different parameters from the same binary
+#[account]
+pub struct GlobalConfig {
+    /// Authority allowed to change protocol settings. Only ever required as a
+    /// signer, never as a payer, so it can be a multisig vault PDA
+    pub admin: Pubkey,
+    /// Proposed admin that must accept before the transfer takes effect
+    pub pending_admin: Option<Pubkey>,
+    pub fee_collector: Pubkey,
+    pub migration_admin: Pubkey,
+    pub trade_fee_bps: u64,
+    /// Part of the trade fee paid to the curve's creator, in bps of the trade
+    pub creator_fee_bps: u64,
+    /// Part of the trade fee paid to the trader's referrer, if any, in bps of the trade
+    pub referral_fee_bps: u64,
+    /// Default graduation target for curves that don't set their own
+    pub migration_threshold: u64,
+    /// Default virtual SOL for curves that don't set their own
+    pub virtual_sol_amount: u64,
+    /// Graduation target for new curves. Each curve keeps the one it was created with
+    pub graduation_target: GraduationTarget,
+    /// Slots the migration admin has to complete an escrowed migration before holders can reclaim
+    pub migration_timeout_slots: u64,
+    pub bump: u8,
+}
+
+impl GlobalConfig {
+    pub fn calculate_fee(&self, amount: u64) -> Result<u64> {
+        let fee = (amount as u128)
+            .checked_mul(self.trade_fee_bps as u128)
+            .ok_or(error!(ErrorCode::MathOverflow))?
+            / 10000;
+
+        Ok(fee as u64)
+    }
+
+    /// Splits a trade fee into the protocol's, the creator's and the referrer's share
+    pub fn split_fee(&self, fee_amount: u64, has_referrer: bool) -> Result<(u64, u64, u64)> {
+        if self.trade_fee_bps == 0 {
+            return Ok((fee_amount, 0, 0));
+        }
+
+        let share = |bps: u64| -> Result<u64> {
+            let fee = mul_div(fee_amount as u128, bps as u128, self.trade_fee_bps as u128)
+                .ok_or(error!(ErrorCode::MathOverflow))?;
+            Ok(fee as u64)
+        };
+
+        let creator_fee = share(self.creator_fee_bps)?;
+        let referral_fee = if has_referrer { share(self.referral_fee_bps)? } else { 0 };
+        let protocol_fee = fee_amount
+            .checked_sub(creator_fee)
+            .and_then(|fee| fee.checked_sub(referral_fee))
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        Ok((protocol_fee, creator_fee, referral_fee))
+    }
+
+    /// Smallest amount that still covers `net_amount` once the trade fee is taken out
+    pub fn gross_up_for_fee(&self, net_amount: u64) -> Result<u64> {
+        let gross = (net_amount as u128)
+            .checked_mul(10000)
+            .and_then(|v| ceil_div(v, 10000 - self.trade_fee_bps as u128))
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        u64::try_from(gross).map_err(|_| error!(ErrorCode::MathOverflow))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::test_utils::global_config;
+
+    #[test]
+    fn split_fee_without_a_referrer_keeps_the_referral_share() {
+        let config = global_config(100, 30, 20);
+        assert_eq!(config.split_fee(10_000, false).unwrap(), (7_000, 3_000, 0));
+    }
+
+    #[test]
+    fn split_fee_with_a_referrer() {
+        let config = global_config(100, 30, 20);
+        assert_eq!(config.split_fee(10_000, true).unwrap(), (5_000, 3_000, 2_000));
+    }
+
+    #[test]
+    fn split_fee_remainder_goes_to_the_protocol() {
+        let config = global_config(100, 30, 20);
+        // 2.1 and 1.4 round down, the protocol takes the rest
+        assert_eq!(config.split_fee(7, true).unwrap(), (4, 2, 1));
+
+        for fee_amount in [0, 1, 3, 99, 10_001, u64::MAX / 10_000] {
+            for has_referrer in [false, true] {
+                let (protocol, creator, referral) = config.split_fee(fee_amount, has_referrer).unwrap();
+                assert_eq!(protocol + creator + referral, fee_amount);
+            }
+        }
+    }
+
+    #[test]
+    fn split_fee_when_shares_take_the_whole_fee() {
+        let config = global_config(10_000, 6_000, 4_000);
+        assert_eq!(config.split_fee(12_345, true).unwrap(), (0, 7_407, 4_938));
+        assert_eq!(config.split_fee(12_345, false).unwrap(), (4_938, 7_407, 0));
+    }
+
+    #[test]
+    fn split_fee_without_a_trade_fee_pays_the_protocol() {
+        assert_eq!(global_config(0, 30, 20).split_fee(500, true).unwrap(), (500, 0, 0));
+    }
+
+    #[test]
+    fn gross_up_without_a_fee_is_the_net_amount() {
+        let config = global_config(0, 0, 0);
+        for net in [0, 1, 1_000_000_007, u64::MAX] {
+            assert_eq!(config.gross_up_for_fee(net).unwrap(), net);
+        }
+    }
+
+    #[test]
+    fn gross_up_covers_the_fee_and_overcharges_by_at_most_one_lamport() {
+        for trade_fee_bps in [1, 100, 250, 999, 1000] {
+            let config = global_config(trade_fee_bps, 0, 0);
+            let kept_bps = (10_000 - trade_fee_bps) as u128;
+            // Exact divisions, one lamport either side of them, and large amounts
+            let exact = kept_bps as u64;
+            for net in [1, 2, 99, exact - 1, exact, exact + 1, 10 * exact, 1_000_000_000, u64::MAX / 20_000] {
+                let gross = config.gross_up_for_fee(net).unwrap();
+
+                // The seller gets at least `net` once the usual fee is taken from the gross
+                assert!(gross - config.calculate_fee(gross).unwrap() >= net, "{trade_fee_bps} {net}");
+
+                // The fee charged, gross - net, is within a lamport of net * bps / (10000 - bps)
+                let charged = (gross - net) as u128;
+                assert!(charged * kept_bps < net as u128 * trade_fee_bps as u128 + kept_bps, "{trade_fee_bps} {net}");
+                assert!(charged * kept_bps >= net as u128 * trade_fee_bps as u128, "{trade_fee_bps} {net}");
+            }
+        }
+    }
+
+    #[test]
+    fn gross_up_overflow_is_an_error() {
+        assert_eq!(
+            global_config(100, 0, 0).gross_up_for_fee(u64::MAX).unwrap_err(),
+            error!(ErrorCode::MathOverflow),
+        );
+    }
+}
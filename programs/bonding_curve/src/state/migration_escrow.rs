@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::utils::error::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default)]
+pub enum EscrowStatus {
+    #[default]
+    Pending,
+    Completed,
+}
+
+/// Holds a graduated curve's SOL and tokens until the migration admin moves them
+/// into an external pool, or until holders reclaim the SOL after the deadline
+#[account]
+pub struct MigrationEscrow {
+    pub mint: Pubkey,
+    pub curve: Pubkey,
+    /// SOL escrowed at graduation, excluding the escrow's rent
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    /// Tokens held outside the curve at graduation, the basis for pro-rata refunds.
+    /// Includes any still in the creator lock
+    pub circulating_supply: u64,
+    pub deadline_slot: u64,
+    pub status: EscrowStatus,
+    pub destination_pool: Pubkey,
+    pub sol_reclaimed: u64,
+    pub tokens_reclaimed: u64,
+    pub bump: u8,
+}
+
+impl MigrationEscrow {
+    /// Pro-rata share of the escrowed SOL for burning `token_amount`, rounded down
+    pub fn calculate_refund(&self, token_amount: u64) -> Result<u64> {
+        let refund = (token_amount as u128)
+            .checked_mul(self.sol_amount as u128)
+            .ok_or(error!(ErrorCode::MathOverflow))?
+            .checked_div(self.circulating_supply as u128)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        u64::try_from(refund).map_err(|_| error!(ErrorCode::MathOverflow))
+    }
+}
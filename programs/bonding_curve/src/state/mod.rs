@@ -1,8 +1,33 @@
 pub mod bonding_curve;
 pub mod curve_config;
 pub mod events;
+pub mod global_config;
+pub mod pool;
+pub mod migration_escrow;
+pub mod subscription;
+pub mod quote;
+pub mod creator_fee_vault;
+pub mod referral_vault;
+pub mod creator_lock;
+pub mod supply_status;
+pub mod allowlist_entry;
+pub mod trader_referral;
+pub mod fee_vault;
+#[cfg(test)]
+pub mod test_utils;
 
 pub use bonding_curve::*;
 pub use curve_config::*;
 pub use events::*;
-
+pub use global_config::*;
+pub use pool::*;
+pub use migration_escrow::*;
+pub use subscription::*;
+pub use quote::*;
+pub use creator_fee_vault::*;
+pub use referral_vault::*;
+pub use creator_lock::*;
+pub use supply_status::*;
+pub use allowlist_entry::*;
+pub use trader_referral::*;
+pub use fee_vault::*;
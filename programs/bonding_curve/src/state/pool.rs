@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+use crate::utils::error::ErrorCode;
+use crate::utils::math::ceil_div;
+
+/// Constant-product pool a curve graduates into. SOL is held by the pool
+/// account itself and tokens by its token vault, both program-owned PDAs.
+#[account]
+pub struct Pool {
+    pub mint: Pubkey,
+    pub lp_mint: Pubkey,
+    /// SOL reserve, tracked explicitly so the pool's rent is never traded
+    pub sol_reserve: u64,
+    pub token_reserve: u64,
+    /// Liquidity created at graduation. It is never minted, so it can never be withdrawn.
+    pub locked_lp: u64,
+    pub fee_bps: u64,
+    pub bump: u8,
+}
+
+impl Pool {
+    /// Total liquidity shares, including the permanently locked graduation liquidity
+    pub fn total_lp_supply(&self, lp_mint_supply: u64) -> Result<u64> {
+        self.locked_lp
+            .checked_add(lp_mint_supply)
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    pub fn calculate_swap_output(&self, amount_in: u64, is_buy: bool) -> Result<u64> {
+        let (reserve_in, reserve_out) = if is_buy {
+            (self.sol_reserve as u128, self.token_reserve as u128)
+        } else {
+            (self.token_reserve as u128, self.sol_reserve as u128)
+        };
+
+        // The fee stays in the pool, growing k for liquidity providers
+        let amount_in_after_fee = (amount_in as u128)
+            .checked_mul(10000 - self.fee_bps as u128)
+            .ok_or(error!(ErrorCode::MathOverflow))?
+            / 10000;
+
+        let numerator = reserve_out
+            .checked_mul(amount_in_after_fee)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        let denominator = reserve_in
+            .checked_add(amount_in_after_fee)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        let amount_out = numerator
+            .checked_div(denominator)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        require!(amount_out < reserve_out, ErrorCode::InsufficientLiquidity);
+
+        Ok(amount_out as u64)
+    }
+
+    /// SOL and tokens required to mint `lp_amount`, rounded up
+    pub fn calculate_deposit(&self, lp_amount: u64, lp_supply: u64) -> Result<(u64, u64)> {
+        let sol_amount = ceil_div(
+            (lp_amount as u128)
+                .checked_mul(self.sol_reserve as u128)
+                .ok_or(error!(ErrorCode::MathOverflow))?,
+            lp_supply as u128,
+        ).ok_or(error!(ErrorCode::MathOverflow))?;
+
+        let token_amount = ceil_div(
+            (lp_amount as u128)
+                .checked_mul(self.token_reserve as u128)
+                .ok_or(error!(ErrorCode::MathOverflow))?,
+            lp_supply as u128,
+        ).ok_or(error!(ErrorCode::MathOverflow))?;
+
+        Ok((
+            u64::try_from(sol_amount).map_err(|_| error!(ErrorCode::MathOverflow))?,
+            u64::try_from(token_amount).map_err(|_| error!(ErrorCode::MathOverflow))?,
+        ))
+    }
+
+    /// SOL and tokens returned for burning `lp_amount`, rounded down
+    pub fn calculate_withdrawal(&self, lp_amount: u64, lp_supply: u64) -> Result<(u64, u64)> {
+        let sol_amount = (lp_amount as u128)
+            .checked_mul(self.sol_reserve as u128)
+            .ok_or(error!(ErrorCode::MathOverflow))?
+            .checked_div(lp_supply as u128)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        let token_amount = (lp_amount as u128)
+            .checked_mul(self.token_reserve as u128)
+            .ok_or(error!(ErrorCode::MathOverflow))?
+            .checked_div(lp_supply as u128)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        // Both are bounded by the reserves since lp_amount <= lp_supply
+        Ok((sol_amount as u64, token_amount as u64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::test_utils::{pool, SOL};
+    use crate::utils::constants::TOKEN_UNIT;
+
+    fn k(pool: &Pool) -> u128 {
+        pool.sol_reserve as u128 * pool.token_reserve as u128
+    }
+
+    #[test]
+    fn swaps_never_shrink_k() {
+        for is_buy in [true, false] {
+            for amount_in in [1, 1_000, SOL, 50 * SOL, 40_000_000 * TOKEN_UNIT as u64] {
+                let mut pool = pool();
+                let before = k(&pool);
+                let amount_out = pool.calculate_swap_output(amount_in, is_buy).unwrap();
+
+                if is_buy {
+                    pool.sol_reserve += amount_in;
+                    pool.token_reserve -= amount_out;
+                } else {
+                    pool.token_reserve += amount_in;
+                    pool.sol_reserve -= amount_out;
+                }
+                assert!(k(&pool) >= before, "amount_in {amount_in}, is_buy {is_buy}");
+            }
+        }
+    }
+
+    #[test]
+    fn the_fee_is_taken_off_the_input() {
+        let mut pool = pool();
+        let with_fee = pool.calculate_swap_output(SOL, true).unwrap();
+
+        // Same as swapping the input less 0.25% with no fee
+        pool.fee_bps = 0;
+        assert_eq!(with_fee, pool.calculate_swap_output(SOL - SOL / 400, true).unwrap());
+        assert!(with_fee < pool.calculate_swap_output(SOL, true).unwrap());
+    }
+
+    #[test]
+    fn swap_output_matches_the_constant_product_formula() {
+        let mut pool = pool();
+        pool.fee_bps = 0;
+
+        // 100M * 10 / (100 + 10), rounded down
+        assert_eq!(pool.calculate_swap_output(10 * SOL, true).unwrap(), 9_090_909_090_909);
+        assert_eq!(pool.calculate_swap_output(0, true).unwrap(), 0);
+    }
+
+    #[test]
+    fn locked_liquidity_counts_towards_the_supply() {
+        let mut pool = pool();
+        pool.locked_lp = 1_000;
+
+        assert_eq!(pool.total_lp_supply(500).unwrap(), 1_500);
+        pool.locked_lp = u64::MAX;
+        assert!(pool.total_lp_supply(1).is_err());
+    }
+
+    #[test]
+    fn deposits_round_up_and_withdrawals_round_down() {
+        let mut pool = pool();
+        pool.sol_reserve = 10;
+        pool.token_reserve = 10;
+
+        // A third of the supply is 3.33 of each reserve
+        assert_eq!(pool.calculate_deposit(1, 3).unwrap(), (4, 4));
+        assert_eq!(pool.calculate_withdrawal(1, 3).unwrap(), (3, 3));
+    }
+
+    #[test]
+    fn depositing_then_withdrawing_never_profits() {
+        let pool = pool();
+        let lp_supply = 1_000_000_007;
+
+        for lp_amount in [1, 999, 123_456_789, lp_supply] {
+            let (sol_in, tokens_in) = pool.calculate_deposit(lp_amount, lp_supply).unwrap();
+            let (sol_out, tokens_out) = pool.calculate_withdrawal(lp_amount, lp_supply).unwrap();
+
+            assert!(sol_out <= sol_in && tokens_out <= tokens_in);
+        }
+        assert_eq!(
+            pool.calculate_withdrawal(lp_supply, lp_supply).unwrap(),
+            (pool.sol_reserve, pool.token_reserve)
+        );
+    }
+}
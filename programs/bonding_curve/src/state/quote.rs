@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+/// Which side of a trade the quoted amount fixes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum TradeDirection {
+    /// Buy an exact token amount, as `buy`
+    BuyExactOut,
+    /// Spend an exact SOL amount, as `buy_with_sol`
+    BuyExactIn,
+    /// Sell an exact token amount, as `sell`
+    SellExactIn,
+    /// Receive an exact SOL amount, as `sell_for_sol`
+    SellExactOut,
+}
+
+/// Real reserves the curve would hold once the trade settles
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct QuoteReserves {
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+}
+
+/// Full breakdown of a trade, matching what the trade instruction would settle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Quote {
+    /// SOL paid including fees for buys, tokens sold for sells
+    pub input: u64,
+    /// Tokens received for buys, SOL received after fees for sells
+    pub output: u64,
+    /// Trade fee in lamports, zero while subscribed
+    pub fee: u64,
+    /// Launch tax on buys in lamports, kept in the curve
+    pub launch_tax: u64,
+    /// Move in spot price caused by the trade
+    pub price_impact_bps: u64,
+    /// Spot price in lamports per whole token, scaled by PRICE_SCALE
+    pub spot_price_before: u64,
+    pub spot_price_after: u64,
+    pub reserves_after: QuoteReserves,
+    /// Whether the trade takes the curve to its graduation target
+    pub would_graduate: bool,
+}
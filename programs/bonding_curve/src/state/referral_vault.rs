@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::utils::error::ErrorCode;
+use crate::state::fee_vault::FeeVault;
+
+/// Registered referrer's share of trade fees, held until the referrer claims it
+#[account]
+pub struct ReferralVault {
+    pub referrer: Pubkey,
+    /// Lamports credited to the vault since registration
+    pub total_accrued: u64,
+    /// Lamports paid out to the referrer so far
+    pub total_claimed: u64,
+    pub bump: u8,
+}
+
+impl ReferralVault {
+    pub fn accrue(&mut self, amount: u64) -> Result<()> {
+        self.total_accrued = self.total_accrued
+            .checked_add(amount)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+
+        Ok(())
+    }
+}
+
+impl FeeVault for ReferralVault {
+    fn total_accrued(&self) -> u64 {
+        self.total_accrued
+    }
+
+    fn total_claimed(&self) -> u64 {
+        self.total_claimed
+    }
+
+    fn set_total_claimed(&mut self, total_claimed: u64) {
+        self.total_claimed = total_claimed;
+    }
+}
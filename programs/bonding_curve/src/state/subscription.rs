@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Subscription {
+    pub user: Pubkey,
+    /// Unix timestamp after which the subscription no longer waives trade fees
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl Subscription {
+    pub fn is_active(&self, now: i64) -> bool {
+        now < self.expires_at
+    }
+}
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// What a scanner needs to confirm a token's supply can't change
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SupplyStatus {
+    /// Set once the curve has dropped its mint authority
+    pub supply_fixed: bool,
+    /// Read from the mint itself rather than trusted from the curve
+    pub mint_authority: Option<Pubkey>,
+    pub freeze_authority: Option<Pubkey>,
+    pub total_supply: u64,
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::utils::constants::{TOKEN_UNIT, POOL_FEE_BPS};
+
+pub const SOL: u64 = 1_000_000_000;
+pub const SUPPLY: u64 = 1_000_000_000 * TOKEN_UNIT as u64;
+
+/// Fresh curve over the whole supply, with 30 SOL against 300M virtual tokens and a
+/// 20 SOL graduation target
+pub fn curve(curve_type: CurveType) -> BondingCurve {
+    let config = CurveConfig {
+        curve_type,
+        virtual_sol_reserves: 30 * SOL,
+        virtual_token_reserves: 300_000_000 * TOKEN_UNIT as u64,
+        graduation_sol_target: 20 * SOL,
+        ..Default::default()
+    };
+    assert!(config.validate(SUPPLY));
+
+    BondingCurve {
+        real_token_reserves: SUPPLY,
+        virtual_sol_reserves: config.virtual_sol_reserves,
+        virtual_token_reserves: config.virtual_token_reserves,
+        token_total_supply: SUPPLY,
+        config,
+        ..Default::default()
+    }
+}
+
+/// Graduated pool holding 100 SOL against 100M tokens, at the protocol's 0.25% fee
+pub fn pool() -> Pool {
+    Pool {
+        mint: Pubkey::default(),
+        lp_mint: Pubkey::default(),
+        sol_reserve: 100 * SOL,
+        token_reserve: 100_000_000 * TOKEN_UNIT as u64,
+        locked_lp: 0,
+        fee_bps: POOL_FEE_BPS,
+        bump: 0,
+    }
+}
+
+pub fn global_config(trade_fee_bps: u64, creator_fee_bps: u64, referral_fee_bps: u64) -> GlobalConfig {
+    GlobalConfig {
+        admin: Pubkey::default(),
+        pending_admin: None,
+        fee_collector: Pubkey::default(),
+        migration_admin: Pubkey::default(),
+        trade_fee_bps,
+        creator_fee_bps,
+        referral_fee_bps,
+        migration_threshold: 0,
+        virtual_sol_amount: 0,
+        graduation_target: GraduationTarget::Pool,
+        migration_timeout_slots: 0,
+        bump: 0,
+    }
+}
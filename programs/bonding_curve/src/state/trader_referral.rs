@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+/// Referrer a trader signed up under. Set once; every later trade credits this referrer's
+/// vault.
+#[account]
+pub struct TraderReferral {
+    pub trader: Pubkey,
+    pub referrer: Pubkey,
+    pub bump: u8,
+}
@@ -1,24 +1,31 @@
-use anchor_lang::prelude::*;
+pub const MAX_TRADE_FEE_BPS: u64 = 1000; // 10%
+/// Largest share of the supply a developer can buy when creating their token
+pub const MAX_DEVELOPER_BUY_BPS: u64 = 1000; // 10%
 
-pub const TRADE_FEE_BPS: u64 = 100; // 1%
+pub const TOKEN_DECIMALS: u8 = 6;
+/// Base units per whole token
+pub const TOKEN_UNIT: u128 = 1_000_000;
+/// Linear curve slopes are expressed per billion whole tokens sold
+pub const SLOPE_SCALE: u128 = 1_000_000_000;
+/// Spot prices are lamports per whole token scaled by this factor
+pub const PRICE_SCALE: u128 = 1_000_000_000;
 
-// Define byte arrays for the public keys
-const FEE_COLLECTOR_BYTES: [u8; 32] = [
-    194, 73, 64, 156, 20, 86, 1, 93,
-    103, 112, 193, 65, 134, 23, 207, 28,
-    187, 39, 81, 4, 122, 135, 79, 54,
-    8, 254, 239, 26, 243, 192, 173, 3
-];
+// Bounds creators must stay within when configuring a curve
+pub const MIN_VIRTUAL_SOL_RESERVES: u64 = 1_000_000_000; // 1 SOL
+pub const MAX_VIRTUAL_SOL_RESERVES: u64 = 1_000_000_000_000; // 1,000 SOL
+pub const MAX_VIRTUAL_TOKEN_RESERVES: u64 = 10_000_000_000_000_000; // 10B whole tokens
+pub const MIN_GRADUATION_SOL_TARGET: u64 = 10_000_000_000; // 10 SOL
+pub const MAX_GRADUATION_SOL_TARGET: u64 = 1_000_000_000_000; // 1,000 SOL
+pub const MAX_ALLOWLIST_DURATION_SECONDS: i64 = 24 * 60 * 60; // 1 day
+pub const MAX_LAUNCH_TAX_BPS: u64 = 5000; // 50%
+pub const MAX_LAUNCH_TAX_DECAY_SLOTS: u64 = 9000; // ~1 hour
 
-const MIGRATION_ADMIN_BYTES: [u8; 32] = [
-    224, 67, 189, 212, 203, 187, 41,
-    223, 118, 50, 190, 182, 44, 210,
-    139, 163, 109, 249, 134, 115, 45,
-    43, 110, 126, 162, 220, 181, 167,
-    50, 69, 161, 200
-];
+pub const POOL_FEE_BPS: u64 = 25; // 0.25%, kept in the pool as liquidity
+pub const LP_DECIMALS: u8 = 9;
 
-// Create Pubkey constants
-pub const FEE_COLLECTOR: Pubkey = Pubkey::new_from_array(FEE_COLLECTOR_BYTES);
-pub const MIGRATION_ADMIN: Pubkey = Pubkey::new_from_array(MIGRATION_ADMIN_BYTES); 
+pub const SUBSCRIPTION_PRICE_PER_PERIOD: u64 = 100_000_000; // 0.1 SOL in lamports
+pub const SUBSCRIPTION_PERIOD_SECONDS: i64 = 30 * 24 * 60 * 60; // 30 days
+pub const MAX_SUBSCRIPTION_PERIODS: u16 = 12;
 
+/// Bumped whenever the layout of BuyEvent or SellEvent changes
+pub const TRADE_EVENT_VERSION: u8 = 1;
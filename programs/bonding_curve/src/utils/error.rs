@@ -34,6 +34,58 @@ pub enum ErrorCode {
     MigrationRequired,
     #[msg("Invalid migration admin")]
     InvalidMigrationAdmin,
-    #[msg("Trading disabled - token has migrated to Raydium")]
+    #[msg("Trading disabled - token has graduated from the bonding curve")]
     MigrationComplete,
+    #[msg("Invalid number of subscription periods")]
+    InvalidSubscriptionPeriods,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Invalid protocol configuration")]
+    InvalidProtocolConfig,
+    #[msg("No admin transfer is pending for this signer")]
+    NoPendingAdmin,
+    #[msg("Curve has not reached its migration threshold")]
+    GraduationNotReady,
+    #[msg("Graduation target is not enabled")]
+    InvalidGraduationTarget,
+    #[msg("Migration escrow is not pending")]
+    EscrowNotPending,
+    #[msg("Migration deadline has passed")]
+    MigrationDeadlinePassed,
+    #[msg("Migration deadline has not passed yet")]
+    MigrationDeadlineNotReached,
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+    #[msg("Traders can't refer themselves")]
+    InvalidReferrer,
+    #[msg("Token metadata has not been created yet")]
+    MetadataNotInitialized,
+    #[msg("Token metadata has already been created")]
+    MetadataAlreadyInitialized,
+    #[msg("Developer buy exceeds the allowed share of supply")]
+    DeveloperBuyTooLarge,
+    #[msg("Developer token account is required for an initial buy")]
+    DeveloperTokenAccountRequired,
+    #[msg("Invalid lock schedule")]
+    InvalidLockSchedule,
+    #[msg("Creator lock account is required")]
+    CreatorLockRequired,
+    #[msg("Mint must not have a freeze authority")]
+    FreezeAuthoritySet,
+    #[msg("Trading has not started yet")]
+    TradingNotStarted,
+    #[msg("Wallet is not on the allowlist")]
+    NotAllowlisted,
+    #[msg("Invalid allowlist proof")]
+    InvalidMerkleProof,
+    #[msg("Purchase exceeds the allowlist cap for this wallet")]
+    AllowlistCapExceeded,
+    #[msg("Buy exceeds the curve's per-transaction cap")]
+    TxCapExceeded,
+    #[msg("Buy would exceed the curve's per-wallet holding cap")]
+    WalletCapExceeded,
+    #[msg("Referral vault doesn't belong to the trader's referrer")]
+    ReferrerMismatch,
+    #[msg("Too few tokens are left to open the pool at the curve's final price")]
+    GraduationLiquidityShortfall,
 }
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+
+/// Does what `emit_cpi!` does for shared helpers, which have no `ctx` for it to read the
+/// event authority from.
+#[derive(Clone)]
+pub struct EventEmitter<'info> {
+    authority: AccountInfo<'info>,
+    bump: u8,
+}
+
+impl<'info> EventEmitter<'info> {
+    pub fn new(authority: &AccountInfo<'info>, bump: u8) -> Self {
+        Self {
+            authority: authority.clone(),
+            bump,
+        }
+    }
+
+    pub fn emit<E: anchor_lang::Event>(&self, event: E) -> Result<()> {
+        #[cfg(feature = "log-events")]
+        emit!(event);
+
+        let data: Vec<u8> = anchor_lang::event::EVENT_IX_TAG_LE
+            .into_iter()
+            .chain(event.data())
+            .collect();
+        let ix = Instruction::new_with_bytes(
+            crate::ID,
+            &data,
+            vec![AccountMeta::new_readonly(self.authority.key(), true)],
+        );
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            &[self.authority.clone()],
+            &[&[b"__event_authority", &[self.bump]]],
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Emits an event from a handler whose accounts are marked `#[event_cpi]`, through a
+/// self-CPI and, with the `log-events` feature, the program log as well
+#[macro_export]
+macro_rules! emit_event {
+    ($ctx:expr, $event:expr) => {{
+        let event = $event;
+        let ctx = &$ctx;
+        #[cfg(feature = "log-events")]
+        ::anchor_lang::emit!(event);
+        ::anchor_lang::emit_cpi!(event);
+    }};
+}
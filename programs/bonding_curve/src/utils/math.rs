@@ -0,0 +1,185 @@
+/// Integer square root, rounded down
+pub fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+
+    // Newton's method starting from a power of two above the root
+    let mut x = 1u128 << ((128 - value.leading_zeros()).div_ceil(2));
+    loop {
+        let y = (x + value / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Division rounded up, used wherever rounding must favor the protocol
+pub fn ceil_div(numerator: u128, denominator: u128) -> Option<u128> {
+    if denominator == 0 {
+        return None;
+    }
+
+    numerator
+        .checked_add(denominator - 1)
+        .map(|n| n / denominator)
+}
+
+/// Fixed-point scale used for exponential pricing
+pub const WAD: u128 = 1_000_000_000_000_000_000;
+/// ln(2) scaled by WAD
+pub const LN2_WAD: u128 = 693_147_180_559_945_309;
+/// Largest whole exponent accepted by `exp2_wad`, keeps results well inside u128
+pub const MAX_EXP2_EXPONENT: u128 = 60;
+
+/// `a * b / c` rounded down, without overflowing on the intermediate product
+/// as long as `a * (b % c)` fits in a u128
+pub fn mul_div(a: u128, b: u128, c: u128) -> Option<u128> {
+    if c == 0 {
+        return None;
+    }
+
+    let whole = a.checked_mul(b / c)?;
+    let remainder = a.checked_mul(b % c)? / c;
+
+    whole.checked_add(remainder)
+}
+
+/// 2^(y / WAD), scaled by WAD
+pub fn exp2_wad(y: u128) -> Option<u128> {
+    let whole = y / WAD;
+    if whole > MAX_EXP2_EXPONENT {
+        return None;
+    }
+
+    // 2^frac = e^(frac * ln2), with frac * ln2 < 0.7 the Taylor series converges quickly
+    let z = (y % WAD).checked_mul(LN2_WAD)? / WAD;
+    let mut sum = WAD;
+    let mut term = WAD;
+    let mut i = 1u128;
+    while term > 0 {
+        term = term.checked_mul(z)? / WAD / i;
+        sum = sum.checked_add(term)?;
+        i += 1;
+    }
+
+    sum.checked_shl(whole as u32)
+}
+
+/// log2(x / WAD) scaled by WAD, rounded down. `x` must be at least WAD.
+pub fn log2_wad(x: u128) -> Option<u128> {
+    if x < WAD {
+        return None;
+    }
+
+    // Integer part
+    let mut whole = 0u128;
+    let mut m = x;
+    while m >= 2 * WAD {
+        m /= 2;
+        whole += 1;
+    }
+
+    // Fractional part, one bit per squaring
+    let mut result = whole.checked_mul(WAD)?;
+    let mut bit = WAD / 2;
+    while bit > 0 {
+        m = m.checked_mul(m)? / WAD;
+        if m >= 2 * WAD {
+            m /= 2;
+            result += bit;
+        }
+        bit /= 2;
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Relative difference between `a` and `b` in parts per WAD
+    fn relative_error(a: u128, b: u128) -> u128 {
+        mul_div(a.abs_diff(b), WAD, b).unwrap()
+    }
+
+    #[test]
+    fn isqrt_rounds_down() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+
+        for value in [2u128, 99, 1_000_000_007, 10u128.pow(30) + 12345] {
+            let root = isqrt(value);
+            assert!(root * root <= value);
+            assert!((root + 1) * (root + 1) > value);
+        }
+    }
+
+    #[test]
+    fn ceil_div_rounds_up() {
+        assert_eq!(ceil_div(10, 5), Some(2));
+        assert_eq!(ceil_div(11, 5), Some(3));
+        assert_eq!(ceil_div(0, 5), Some(0));
+        assert_eq!(ceil_div(1, 0), None);
+        assert_eq!(ceil_div(u128::MAX, 1), Some(u128::MAX));
+    }
+
+    #[test]
+    fn mul_div_matches_wide_arithmetic() {
+        assert_eq!(mul_div(7, 3, 2), Some(10));
+        assert_eq!(mul_div(1, 1, 0), None);
+        // a * b overflows u128 but the result fits
+        assert_eq!(mul_div(10u128.pow(30), 10u128.pow(18), 10u128.pow(18)), Some(10u128.pow(30)));
+        assert_eq!(mul_div(u128::MAX, 2, 2), Some(u128::MAX));
+        assert_eq!(mul_div(u128::MAX, 2, 1), None);
+    }
+
+    #[test]
+    fn exp2_wad_hits_exact_powers() {
+        assert_eq!(exp2_wad(0), Some(WAD));
+        for power in [1u32, 2, 10, 32, 60] {
+            let result = exp2_wad(power as u128 * WAD).unwrap();
+            assert_eq!(result, WAD << power);
+        }
+    }
+
+    #[test]
+    fn exp2_wad_fractions_are_accurate() {
+        // 2^0.5 and 2^0.25
+        let sqrt2 = 1_414_213_562_373_095_048u128;
+        let fourth_root2 = 1_189_207_115_002_721_066u128;
+        assert!(relative_error(exp2_wad(WAD / 2).unwrap(), sqrt2) <= 1_000);
+        assert!(relative_error(exp2_wad(WAD / 4).unwrap(), fourth_root2) <= 1_000);
+    }
+
+    #[test]
+    fn exp2_wad_overflow_boundary() {
+        assert!(exp2_wad(MAX_EXP2_EXPONENT * WAD).is_some());
+        assert!(exp2_wad((MAX_EXP2_EXPONENT + 1) * WAD - 1).is_some());
+        assert_eq!(exp2_wad((MAX_EXP2_EXPONENT + 1) * WAD), None);
+    }
+
+    #[test]
+    fn log2_wad_hits_exact_powers() {
+        assert_eq!(log2_wad(WAD - 1), None);
+        assert_eq!(log2_wad(WAD), Some(0));
+        for power in [1u32, 7, 60] {
+            assert_eq!(log2_wad(WAD << power), Some(power as u128 * WAD));
+        }
+    }
+
+    #[test]
+    fn exp2_and_log2_round_trip() {
+        for y in [1u128, WAD / 3, WAD, 5 * WAD / 2, 17 * WAD + 123_456_789, 60 * WAD + WAD / 7] {
+            let round_trip = log2_wad(exp2_wad(y).unwrap()).unwrap();
+            // log2 rounds down, so the trip may only lose a little
+            assert!(round_trip <= y + 1_000);
+            assert!(y - round_trip.min(y) <= 1_000_000);
+        }
+    }
+}
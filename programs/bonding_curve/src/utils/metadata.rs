@@ -1,9 +1,19 @@
 use anchor_lang::prelude::*;
-use mpl_token_metadata::instructions::{CreateMetadataAccountV3, CreateMetadataAccountV3InstructionArgs};
-use mpl_token_metadata::types::DataV2;
+use anchor_spl::token_interface::TokenMetadataUpdateAuthority;
+use anchor_spl::token_2022_extensions::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use mpl_token_metadata::accounts::Metadata;
+use mpl_token_metadata::instructions::{
+    CreateMetadataAccountV3,
+    CreateMetadataAccountV3InstructionArgs,
+    SignMetadata,
+    UpdateMetadataAccountV2,
+    UpdateMetadataAccountV2InstructionArgs,
+};
+use mpl_token_metadata::types::{Collection, Creator, DataV2};
 use solana_program::instruction::Instruction;
 use solana_program::{system_program, sysvar};
 use crate::utils::error::ErrorCode;
+use crate::state::BondingCurve;
 
 pub fn find_metadata_account(mint: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
@@ -41,23 +51,34 @@ pub fn validate_metadata_account(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_metadata_ix(
     metadata: Pubkey,
     mint: Pubkey,
     mint_authority: Pubkey,
     payer: Pubkey,
     update_authority: Pubkey,
+    developer: Pubkey,
+    collection_mint: Pubkey,
     name: String,
     symbol: String,
     uri: String,
 ) -> Result<Instruction> {
+    // Both start unverified, the developer and the collection authority sign for them afterwards
     let data_v2 = DataV2 {
         name,
         symbol,
         uri,
         seller_fee_basis_points: 0,
-        creators: None,
-        collection: None,
+        creators: Some(vec![Creator {
+            address: developer,
+            verified: false,
+            share: 100,
+        }]),
+        collection: Some(Collection {
+            verified: false,
+            key: collection_mint,
+        }),
         uses: None,
     };
 
@@ -71,7 +92,113 @@ pub fn create_metadata_ix(
         rent: Some(sysvar::rent::ID),
     }.instruction(CreateMetadataAccountV3InstructionArgs {
         data: data_v2,
-        is_mutable: false,
+        is_mutable: true,
         collection_details: None,
     }))
-}
\ No newline at end of file
+}
+
+/// Marks the developer as a verified creator. The developer must have signed the transaction
+pub fn verify_creator<'info>(
+    metadata: &AccountInfo<'info>,
+    developer: &AccountInfo<'info>,
+    metadata_program: &AccountInfo<'info>,
+) -> Result<()> {
+    anchor_lang::solana_program::program::invoke(
+        &SignMetadata {
+            metadata: metadata.key(),
+            creator: developer.key(),
+        }.instruction(),
+        &[
+            metadata.clone(),
+            developer.clone(),
+            metadata_program.clone(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// Current data of a Metaplex metadata account, in the shape updates expect
+pub fn read_metadata_data(metadata: &AccountInfo) -> Result<DataV2> {
+    let metadata = Metadata::safe_deserialize(&metadata.try_borrow_data()?)
+        .map_err(|_| error!(ErrorCode::InvalidMetadataAddress))?;
+
+    // Metaplex pads these with null bytes
+    Ok(DataV2 {
+        name: metadata.name.trim_end_matches('\0').to_string(),
+        symbol: metadata.symbol.trim_end_matches('\0').to_string(),
+        uri: metadata.uri.trim_end_matches('\0').to_string(),
+        seller_fee_basis_points: metadata.seller_fee_basis_points,
+        creators: metadata.creators,
+        collection: metadata.collection,
+        uses: metadata.uses,
+    })
+}
+
+pub fn update_metadata_ix(
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    data: Option<DataV2>,
+    is_mutable: Option<bool>,
+) -> Instruction {
+    UpdateMetadataAccountV2 {
+        metadata,
+        update_authority,
+    }.instruction(UpdateMetadataAccountV2InstructionArgs {
+        data,
+        new_update_authority: None,
+        primary_sale_happened: None,
+        is_mutable,
+    })
+}
+
+/// Makes a curve's metadata permanent, whether it lives in Metaplex or in the
+/// Token-2022 mint itself
+pub fn lock_metadata<'info>(
+    curve: &Account<'info, BondingCurve>,
+    mint: &AccountInfo<'info>,
+    metadata: &AccountInfo<'info>,
+    metadata_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let curve_seeds: &[&[u8]] = &[
+        b"bonding_curve",
+        curve.config.developer.as_ref(),
+        curve.token_seed.as_ref(),
+        &[curve.bump],
+    ];
+
+    if curve.native_metadata {
+        // Dropping the update authority freezes the mint's metadata extension
+        require!(metadata.key() == mint.key(), ErrorCode::InvalidMetadataAddress);
+
+        return anchor_spl::token_interface::token_metadata_update_authority(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TokenMetadataUpdateAuthority {
+                    token_program_id: token_program.clone(),
+                    metadata: metadata.clone(),
+                    current_authority: curve.to_account_info(),
+                    new_authority: curve.to_account_info(),
+                },
+                &[curve_seeds],
+            ),
+            OptionalNonZeroPubkey::default(),
+        );
+    }
+
+    require!(metadata_program.key() == mpl_token_metadata::ID, ErrorCode::InvalidMetadataProgram);
+    require!(metadata.key() == find_metadata_account(&mint.key()).0, ErrorCode::InvalidMetadataAddress);
+
+    anchor_lang::solana_program::program::invoke_signed(
+        &update_metadata_ix(metadata.key(), curve.key(), None, Some(false)),
+        &[
+            metadata.clone(),
+            curve.to_account_info(),
+            metadata_program.clone(),
+        ],
+        &[curve_seeds],
+    )?;
+
+    Ok(())
+}
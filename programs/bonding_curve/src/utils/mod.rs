@@ -2,8 +2,14 @@ pub mod metadata;
 pub mod validation;
 pub mod error;
 pub mod constants;
+pub mod math;
+pub mod event_cpi;
+pub mod trade_fees;
 
 pub use metadata::*;
 pub use validation::*;
 pub use error::*;
 pub use constants::*;
+pub use math::*;
+pub use event_cpi::*;
+pub use trade_fees::*;
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::utils::error::ErrorCode;
+use crate::utils::event_cpi::EventEmitter;
+
+/// Account a trade's SOL is paid out of
+pub enum FeePayer<'a, 'info> {
+    /// A buyer's wallet, paying through the system program
+    Wallet {
+        wallet: &'a AccountInfo<'info>,
+        system_program: &'a AccountInfo<'info>,
+    },
+    /// The curve, paying out of a sale. It's program-owned, so its lamports move directly
+    Curve(&'a AccountInfo<'info>),
+}
+
+impl<'a, 'info> FeePayer<'a, 'info> {
+    pub fn pay(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        match self {
+            FeePayer::Wallet { wallet, system_program } => anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    (*system_program).clone(),
+                    anchor_lang::system_program::Transfer {
+                        from: (*wallet).clone(),
+                        to: to.clone(),
+                    },
+                ),
+                amount,
+            ),
+            FeePayer::Curve(curve) => {
+                let curve_lamports = curve.lamports();
+                let to_lamports = to.lamports();
+
+                **curve.try_borrow_mut_lamports()? = curve_lamports
+                    .checked_sub(amount)
+                    .ok_or(error!(ErrorCode::MathOverflow))?;
+                **to.try_borrow_mut_lamports()? = to_lamports
+                    .checked_add(amount)
+                    .ok_or(error!(ErrorCode::MathOverflow))?;
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Sends the protocol's share of a trade fee to the fee collector and the creator's and
+/// referrer's to their vaults, returning the referrer's share
+#[allow(clippy::too_many_arguments)]
+pub fn pay_trade_fees<'info>(
+    global_config: &GlobalConfig,
+    mint: Pubkey,
+    payer: &FeePayer<'_, 'info>,
+    fee_collector: &AccountInfo<'info>,
+    creator_fee_vault: &mut Account<'info, CreatorFeeVault>,
+    referral_vault: Option<&mut Account<'info, ReferralVault>>,
+    fee_amount: u64,
+    events: &EventEmitter<'info>,
+) -> Result<u64> {
+    let (protocol_fee, creator_fee, referral_fee) = global_config
+        .split_fee(fee_amount, referral_vault.is_some())?;
+
+    if protocol_fee > 0 {
+        payer.pay(fee_collector, protocol_fee)?;
+    }
+
+    if creator_fee > 0 {
+        payer.pay(&creator_fee_vault.to_account_info(), creator_fee)?;
+        creator_fee_vault.accrue(creator_fee)?;
+
+        events.emit(CreatorFeeAccruedEvent {
+            mint,
+            curve: creator_fee_vault.curve,
+            amount: creator_fee,
+            total_accrued: creator_fee_vault.total_accrued,
+        })?;
+    }
+
+    if let Some(referral_vault) = referral_vault {
+        if referral_fee > 0 {
+            payer.pay(&referral_vault.to_account_info(), referral_fee)?;
+            referral_vault.accrue(referral_fee)?;
+
+            events.emit(ReferralFeeAccruedEvent {
+                mint,
+                referrer: referral_vault.referrer,
+                referral_vault: referral_vault.key(),
+                amount: referral_fee,
+                total_accrued: referral_vault.total_accrued,
+            })?;
+        }
+    }
+
+    Ok(referral_fee)
+}
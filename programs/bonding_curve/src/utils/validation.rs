@@ -8,4 +8,63 @@ pub fn verify_token_balance(token_account: &Account<TokenAccount>, expected: u64
         ErrorCode::BalanceMismatch
     );
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Checks `leaf` against a Merkle root built from keccak hashes of sorted pairs
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            solana_program::keccak::hashv(&[&node, sibling]).0
+        } else {
+            solana_program::keccak::hashv(&[sibling, &node]).0
+        }
+    });
+
+    computed == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::keccak::{hash, hashv};
+
+    fn leaf(byte: u8) -> [u8; 32] {
+        hash(&[byte; 32]).0
+    }
+
+    fn parent(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        if a <= b { hashv(&[&a, &b]).0 } else { hashv(&[&b, &a]).0 }
+    }
+
+    /// Root over leaves 1-4, built the way the allowlist tooling builds it
+    fn tree() -> ([u8; 32], [u8; 32], [u8; 32]) {
+        let left = parent(leaf(1), leaf(2));
+        let right = parent(leaf(3), leaf(4));
+        (parent(left, right), left, right)
+    }
+
+    #[test]
+    fn accepts_a_valid_proof_from_either_side() {
+        let (root, left, right) = tree();
+
+        assert!(verify_merkle_proof(&[leaf(2), right], &root, leaf(1)));
+        assert!(verify_merkle_proof(&[leaf(3), left], &root, leaf(4)));
+    }
+
+    #[test]
+    fn rejects_a_leaf_outside_the_tree() {
+        let (root, _, right) = tree();
+
+        assert!(!verify_merkle_proof(&[leaf(2), right], &root, leaf(5)));
+        assert!(!verify_merkle_proof(&[leaf(2), right], &root, leaf(3)));
+    }
+
+    #[test]
+    fn rejects_proofs_of_the_wrong_length() {
+        let (root, left, right) = tree();
+
+        assert!(!verify_merkle_proof(&[], &root, leaf(1)));
+        assert!(!verify_merkle_proof(&[leaf(2)], &root, leaf(1)));
+        assert!(!verify_merkle_proof(&[leaf(2), right, left], &root, leaf(1)));
+    }
+}